@@ -0,0 +1,15 @@
+/// # `flag_value`
+/// Takes the command line arguments and returns the value following the given flag, such as `--colouring slope`.
+/// `None` if the flag is not given or has no value
+pub fn flag_value<'a>(args: &'a [String], flag: &str) -> Option<&'a str> {
+    args.iter()
+        .position(|arg| arg == flag)
+        .and_then(|idx| args.get(idx + 1))
+        .map(|value| value.as_str())
+}
+
+/// # `has_flag`
+/// Takes the command line arguments and checks if the given flag, such as `--jitter`, is among them
+pub fn has_flag(args: &[String], flag: &str) -> bool {
+    args.iter().any(|arg| arg == flag)
+}
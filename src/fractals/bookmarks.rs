@@ -0,0 +1,150 @@
+use crate::math::vector::Vector2;
+
+use std::fs::File;
+use std::io::{Error, ErrorKind, Read, Write};
+
+// Consts
+pub const BOOKMARK_FILE : &str = "bookmarks.txt";
+
+/// # `Bookmark`
+/// A saved location of a fractal, with everything needed to show it the same way again
+#[derive(Clone, Debug, PartialEq)]
+pub struct Bookmark {
+    pub name: String,
+    pub fractal: String, // Name of the fractal as given on the command line
+    pub centre: Vector2<f64>,
+    pub scale: f64,
+    pub rotation: f32,
+    pub max_iteration: usize,
+    pub colouring: String,
+    pub interior: String,
+    pub palette_offset: f32
+}
+
+impl Bookmark {
+    /// # `parse`
+    /// Reads a bookmark from a line of the bookmark file written as
+    /// `name fractal centre_x centre_y scale rotation max_iteration colouring interior palette_offset`
+    fn parse(line: &str) -> Option<Bookmark> {
+        let fields : Vec<&str> = line.split_whitespace().collect();
+        if fields.len() != 10 {
+            return None;
+        }
+
+        Some(Bookmark {
+            name: fields[0].to_string(),
+            fractal: fields[1].to_string(),
+            centre: Vector2::new(fields[2].parse().ok()?, fields[3].parse().ok()?),
+            scale: fields[4].parse().ok()?,
+            rotation: fields[5].parse().ok()?,
+            max_iteration: fields[6].parse().ok()?,
+            colouring: fields[7].to_string(),
+            interior: fields[8].to_string(),
+            palette_offset: fields[9].parse().ok()?
+        })
+    }
+
+    /// # `to_line`
+    /// Writes the bookmark as a line of the bookmark file
+    fn to_line(&self) -> String {
+        format!("{} {} {} {} {} {} {} {} {} {}", self.name, self.fractal, self.centre.x, self.centre.y,
+                self.scale, self.rotation, self.max_iteration, self.colouring, self.interior, self.palette_offset)
+    }
+
+    /// # `load_all`
+    /// Loads every bookmark from the given file, a missing file has no bookmarks.
+    /// Returns an error if the file can not be read or has an invalid line
+    pub fn load_all(path: &str) -> std::io::Result<Vec<Bookmark>> {
+        let mut text = String::new();
+        match File::open(path) {
+            Ok(mut file) => file.read_to_string(&mut text)?,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err)
+        };
+
+        text.lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(number, line)| Bookmark::parse(line)
+                .ok_or_else(|| Error::new(ErrorKind::InvalidData, format!("\"{}\" line {}: invalid bookmark", path, number + 1))))
+            .collect()
+    }
+
+    /// # `save_all`
+    /// Saves the given bookmarks to the given file, replacing its content
+    pub fn save_all(path: &str, bookmarks: &[Bookmark]) -> std::io::Result<()> {
+        let mut text = String::new();
+        for bookmark in bookmarks.iter() {
+            text.push_str(&bookmark.to_line());
+            text.push('\n');
+        }
+
+        File::create(path)?.write_all(text.as_bytes())
+    }
+
+    /// # `find`
+    /// Finds the bookmark with the given name in the given file. Returns an error if there is no such bookmark
+    pub fn find(path: &str, name: &str) -> std::io::Result<Bookmark> {
+        Bookmark::load_all(path)?
+            .into_iter()
+            .find(|bookmark| bookmark.name == name)
+            .ok_or_else(|| Error::new(ErrorKind::NotFound, format!("no bookmark named \"{}\"", name)))
+    }
+
+    /// # `add`
+    /// Adds the bookmark to the given file, replacing any bookmark with the same name
+    pub fn add(path: &str, bookmark: Bookmark) -> std::io::Result<()> {
+        let mut bookmarks = Bookmark::load_all(path)?;
+        bookmarks.retain(|other| other.name != bookmark.name);
+        bookmarks.push(bookmark);
+        Bookmark::save_all(path, &bookmarks)
+    }
+
+    /// # `delete`
+    /// Deletes the bookmark with the given name from the given file. Returns whether there was such a bookmark
+    pub fn delete(path: &str, name: &str) -> std::io::Result<bool> {
+        let mut bookmarks = Bookmark::load_all(path)?;
+        let count = bookmarks.len();
+        bookmarks.retain(|bookmark| bookmark.name != name);
+        Bookmark::save_all(path, &bookmarks)?;
+        Ok(bookmarks.len() != count)
+    }
+
+    /// # `unused_name`
+    /// Returns a name such as `mandelbrot_3` for the given fractal that no bookmark in the given file has yet
+    pub fn unused_name(path: &str, fractal: &str) -> std::io::Result<String> {
+        let bookmarks = Bookmark::load_all(path)?;
+        let name = (1..)
+            .map(|n| format!("{}_{}", fractal, n))
+            .find(|name| bookmarks.iter().all(|bookmark| &bookmark.name != name))
+            .unwrap();
+        Ok(name)
+    }
+}
+
+/// # `command`
+/// Runs the bookmark command line, `bookmarks` (or `bookmarks list`) lists the bookmarks and `bookmarks delete <name>` deletes one
+pub fn command(args: &[String]) {
+    match args.get(2).map(|arg| arg.as_str()) {
+        None | Some("list") => {
+            let bookmarks = Bookmark::load_all(BOOKMARK_FILE).unwrap_or_else(|err| panic!("Could not load the bookmarks: {}", err));
+            if bookmarks.is_empty() {
+                println!("No bookmarks saved in \"{}\"", BOOKMARK_FILE);
+            }
+            for bookmark in bookmarks.iter() {
+                println!("{}: {} at ({}, {}) scale {:e} rotation {} - {} iterations, {} / {} interior, palette offset {}",
+                         bookmark.name, bookmark.fractal, bookmark.centre.x, bookmark.centre.y, bookmark.scale, bookmark.rotation,
+                         bookmark.max_iteration, bookmark.colouring, bookmark.interior, bookmark.palette_offset);
+            }
+        },
+        Some("delete") => {
+            let name = args.get(3).unwrap_or_else(|| panic!("Give the name of the bookmark to delete"));
+            match Bookmark::delete(BOOKMARK_FILE, name) {
+                Ok(true) => println!("Deleted bookmark \"{}\"", name),
+                Ok(false) => println!("No bookmark named \"{}\"", name),
+                Err(err) => panic!("Could not delete the bookmark: {}", err)
+            }
+        },
+        Some(other) => panic!("No such bookmark command \"{}\"", other)
+    }
+}
@@ -0,0 +1,181 @@
+use crate::visuals::visualizer::{Visualizer, U_WIDTH, U_HEIGHT, HEIGHT, WIDTH};
+use crate::math::{
+    vector::Vector2,
+    viewport::{Viewport, ScreenMap},
+    random::Random
+};
+use super::mandelbrot::kernel;
+use minifb::{Key, KeyRepeat};
+
+use std::fs::File;
+use std::io::{Read, Write};
+
+// Consts
+const CHANNEL_LIMITS : [usize; 3] = [5000, 500, 50]; // Iteration limits of the red, green and blue channels (Nebulabrot)
+const SAMPLES_PER_FRAME : usize = 20000;
+const SAMPLE_RADIUS : f32 = 2.0; // c values are sampled from the square [-2, 2] x [-2, 2]
+const CENTER : Vector2 = Vector2 {x: -0.5, y: 0.0};
+const SCALE : f32 = 3.2; // Width of the view in the complex plane
+const ACCUMULATOR_FILE : &str = "buddhabrot.acc";
+
+/// # `Buddhabrot`
+/// Structure that progressively renders the Buddhabrot (Nebulabrot) by accumulating the orbits of escaping points
+pub struct Buddhabrot {
+    visualizer: Visualizer,
+    viewport: Viewport,
+    random: Random,
+    accumulator: Vec<u32>, // Hit counts, 3 channels (red, green, blue) per pixel
+    samples: u64,
+    orbit: Vec<Vector2>, // Reused between samples to avoid allocations
+    status: String // Outcome of the last load or save, shown in the window title
+}
+
+impl Buddhabrot {
+    /// # `new`
+    /// Initializes the Buddhabrot visualizer, resuming from the saved accumulator if there is one
+    pub fn new() -> Buddhabrot {
+        let mut buddhabrot = Buddhabrot {
+            visualizer: Visualizer::new(None),
+            viewport: Viewport::new(CENTER, SCALE, Vector2::new(WIDTH, HEIGHT)),
+            random: Random::from_time(),
+            accumulator: vec![0; U_WIDTH * U_HEIGHT * 3],
+            samples: 0,
+            orbit: Vec::with_capacity(CHANNEL_LIMITS[0]),
+            status: String::new()
+        };
+
+        buddhabrot.status = match buddhabrot.load() {
+            Ok(()) => format!("resumed from \"{}\"", ACCUMULATOR_FILE),
+            Err(err) => format!("new render ({})", err)
+        };
+
+        buddhabrot
+    }
+
+    /// # `to_pixel`
+    /// Maps a point in the complex plane to the index of its pixel in the window, `None` if it is outside of the window
+    fn to_pixel(screen: &ScreenMap, z: Vector2) -> Option<usize> {
+        let pixel = screen.world_to_pixel(z);
+        let (px, py) = (pixel.x, pixel.y);
+
+        if px < 0.0 || py < 0.0 || px >= WIDTH || py >= HEIGHT {
+            return None;
+        }
+        Some(px as usize + py as usize * U_WIDTH)
+    }
+
+    /// # `sample`
+    /// Traces the orbit of one random c and adds it to the channels whose iteration limit it escaped within
+    fn sample(&mut self, screen: &ScreenMap) {
+        let c = Vector2::new(self.random.range(-SAMPLE_RADIUS, SAMPLE_RADIUS), self.random.range(-SAMPLE_RADIUS, SAMPLE_RADIUS));
+        self.samples += 1;
+
+        // Points inside the main bulbs never escape so they can be skipped
+        if kernel::in_main_bulbs(c) {
+            return;
+        }
+
+        // Same iteration as the Mandelbrot set, but the orbit is remembered
+        let mut z = Vector2::new(0.0, 0.0);
+        self.orbit.clear();
+        while z.x * z.x + z.y * z.y <= 4.0 && self.orbit.len() < CHANNEL_LIMITS[0] {
+            z = Vector2::new(z.x * z.x - z.y * z.y + c.x, 2.0 * z.x * z.y + c.y);
+            self.orbit.push(z);
+        }
+
+        // Points that never escaped are part of the Mandelbrot set and are not drawn
+        let escaped_at = self.orbit.len();
+        if z.x * z.x + z.y * z.y <= 4.0 {
+            return;
+        }
+
+        for point in self.orbit.iter() {
+            if let Some(idx) = Buddhabrot::to_pixel(screen, *point) {
+                for (channel, limit) in CHANNEL_LIMITS.iter().enumerate() {
+                    if escaped_at <= *limit {
+                        self.accumulator[idx * 3 + channel] += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    /// # `tone_map`
+    /// Converts the hit counts into colours, each channel is normalized by its own maximum and square rooted to bring out the faint orbits
+    fn tone_map(&mut self) {
+        let mut max = [1u32; 3];
+        for (i, count) in self.accumulator.iter().enumerate() {
+            max[i % 3] = max[i % 3].max(*count);
+        }
+
+        for (idx, pixel) in self.visualizer.buffer.iter_mut().enumerate() {
+            let mut colour = 0;
+            for (channel, (count, max)) in self.accumulator[idx * 3..idx * 3 + 3].iter().zip(max).enumerate() {
+                let value = (*count as f32 / max as f32).sqrt();
+                colour |= ((value * 255.0) as u32) << (16 - 8 * channel);
+            }
+            *pixel = colour;
+        }
+    }
+
+    /// # `save`
+    /// Saves the sample count and the accumulator to the accumulator file so that the render can be resumed later
+    fn save(&self) -> std::io::Result<()> {
+        let mut bytes = Vec::with_capacity(8 + self.accumulator.len() * 4);
+        bytes.extend_from_slice(&self.samples.to_le_bytes());
+        for count in self.accumulator.iter() {
+            bytes.extend_from_slice(&count.to_le_bytes());
+        }
+
+        File::create(ACCUMULATOR_FILE)?.write_all(&bytes)
+    }
+
+    /// # `load`
+    /// Loads the sample count and accumulator from the accumulator file.
+    /// Returns an error if there is no such file or if it was saved with a different window size
+    fn load(&mut self) -> std::io::Result<()> {
+        let mut bytes = Vec::new();
+        File::open(ACCUMULATOR_FILE)?.read_to_end(&mut bytes)?;
+
+        if bytes.len() != 8 + self.accumulator.len() * 4 {
+            return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "accumulator size does not match the window"));
+        }
+
+        self.samples = u64::from_le_bytes(bytes[0..8].try_into().unwrap());
+        for (count, chunk) in self.accumulator.iter_mut().zip(bytes[8..].chunks_exact(4)) {
+            *count = u32::from_le_bytes(chunk.try_into().unwrap());
+        }
+        Ok(())
+    }
+
+    /// # `draw`
+    /// Keeps sampling and refining the image until the window is closed. Press S to save the accumulator, it is also saved when the window closes
+    pub fn draw(&mut self) {
+        while self.visualizer.window.is_open() {
+            if let Some(screen) = self.viewport.screen_map() {
+                for _ in 0..SAMPLES_PER_FRAME {
+                    self.sample(&screen);
+                }
+            }
+
+            self.tone_map();
+            self.visualizer.window.set_title(&format!("Buddhabrot - {} samples - {}", self.samples, self.status));
+            self.visualizer.apply_buffer();
+
+            if self.visualizer.window.is_key_pressed(Key::S, KeyRepeat::No) {
+                self.save_and_report();
+            }
+        }
+
+        self.save_and_report();
+    }
+
+    /// # `save_and_report`
+    /// Saves the accumulator and shows the outcome in the window title
+    fn save_and_report(&mut self) {
+        self.status = match self.save() {
+            Ok(()) => format!("saved {} samples to \"{}\"", self.samples, ACCUMULATOR_FILE),
+            Err(err) => format!("could not save ({})", err)
+        };
+    }
+}
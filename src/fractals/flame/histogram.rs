@@ -0,0 +1,71 @@
+/// # `Histogram`
+/// Counts how many points landed in every cell along with the sum of their colours.
+/// Every pixel is split into `supersample` x `supersample` cells which are averaged when rendering
+pub struct Histogram {
+    pub width: usize, // Size of the rendered image in pixels
+    pub height: usize,
+    pub supersample: usize,
+    colours: Vec<[f32; 3]>, // Red, green and blue sums of every cell, in the range [0, 1] per point
+    counts: Vec<u32> // Points that landed in every cell
+}
+
+impl Histogram {
+    /// # `new`
+    /// Creates an empty histogram for an image of the given size with `supersample` x `supersample` cells per pixel
+    pub fn new(width: usize, height: usize, supersample: usize) -> Histogram {
+        Histogram {
+            width: width,
+            height: height,
+            supersample: supersample,
+            colours: vec![[0.0; 3]; width * height * supersample * supersample],
+            counts: vec![0; width * height * supersample * supersample]
+        }
+    }
+
+    /// # `add`
+    /// Adds a point of the given colour (0xRRGGBB) to the cell with the given index, cells are indexed row by row
+    pub fn add(&mut self, cell: usize, colour: u32) {
+        let sum = &mut self.colours[cell];
+        sum[0] += ((colour >> 16) & 0xFF) as f32 / 255.0;
+        sum[1] += ((colour >> 8) & 0xFF) as f32 / 255.0;
+        sum[2] += (colour & 0xFF) as f32 / 255.0;
+        self.counts[cell] = self.counts[cell].saturating_add(1);
+    }
+
+    /// # `render`
+    /// Converts the histogram into pixels (0xRRGGBB). The brightness of a cell is the log of its count relative to the log of the largest count,
+    /// so both the dense and the sparse parts of the flame show, and is then gamma corrected. The cells of a pixel are averaged
+    pub fn render(&self, gamma: f32) -> Vec<u32> {
+        let max = self.counts.iter().copied().max().unwrap_or(0);
+        let log_max = (1.0 + max as f32).ln().max(f32::EPSILON);
+        let cell_width = self.width * self.supersample;
+        let samples = (self.supersample * self.supersample) as f32;
+
+        let mut pixels = vec![0; self.width * self.height];
+        for py in 0..self.height {
+            for px in 0..self.width {
+                let mut sum = [0.0f32; 3];
+                for sy in 0..self.supersample {
+                    for sx in 0..self.supersample {
+                        let cell = px * self.supersample + sx + (py * self.supersample + sy) * cell_width;
+                        let count = self.counts[cell];
+                        if count == 0 {
+                            continue;
+                        }
+
+                        // Average colour of the cell scaled by its log density
+                        let brightness = ((1.0 + count as f32).ln() / log_max).powf(1.0 / gamma);
+                        for (total, colour) in sum.iter_mut().zip(self.colours[cell]) {
+                            *total += colour / count as f32 * brightness;
+                        }
+                    }
+                }
+
+                let channel = |value: f32| ((value / samples).min(1.0) * 255.0).round() as u32;
+                pixels[px + py * self.width] = (channel(sum[0]) << 16) | (channel(sum[1]) << 8) | channel(sum[2]);
+            }
+        }
+
+        pixels
+    }
+}
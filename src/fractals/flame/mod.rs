@@ -0,0 +1,193 @@
+use crate::visuals::{
+    visualizer::{Visualizer, U_WIDTH, U_HEIGHT, HEIGHT, WIDTH},
+    colours};
+use crate::math::{
+    vector::Vector2,
+    viewport::Viewport,
+    random::Random
+};
+use crate::cli;
+use super::ifs::SETTLE_ITERATIONS;
+
+use system::{FlameSystem, PRESETS};
+use histogram::Histogram;
+
+// Consts
+const DEFAULT_FLAME : &str = "swirl";
+const DEFAULT_GAMMA : f32 = 2.2;
+const DEFAULT_SUPERSAMPLE : usize = 2;
+const POINTS_PER_FRAME : usize = 200000;
+const FIT_SAMPLES : usize = 50000; // Points used to find the extent of the flame
+const FIT_QUANTILE : f32 = 0.01; // Fraction of the points on each side left out of the view, the variations can throw a few points very far
+const MARGIN : f32 = 1.1; // How much larger than the fitted points the view is
+const PALETTE : [u32; 5] = [colours::NAVY, colours::BLUE, colours::CERISE, colours::GOLD, colours::WHITE]; // Colour index 0 to 1
+
+/// # `Settings`
+/// Options of the flame renderer that can be given from the command line
+pub struct Settings {
+    pub flame: String, // Preset name or path of a flame file
+    pub gamma: f32,
+    pub supersample: usize
+}
+
+impl Settings {
+    /// # `from_args`
+    /// Reads the settings from the command line arguments, `flame [preset|file] [--gamma <gamma>] [--supersample <n>]`
+    pub fn from_args(args: &[String]) -> Settings {
+        let gamma = cli::flag_value(args, "--gamma")
+            .map(|gamma| gamma.parse::<f32>().ok().filter(|gamma| *gamma > 0.0).unwrap_or_else(|| panic!("Invalid gamma \"{}\"", gamma)))
+            .unwrap_or(DEFAULT_GAMMA);
+
+        let supersample = cli::flag_value(args, "--supersample")
+            .map(|n| n.parse::<usize>().ok().filter(|n| *n > 0).unwrap_or_else(|| panic!("Invalid supersampling \"{}\"", n)))
+            .unwrap_or(DEFAULT_SUPERSAMPLE);
+
+        Settings {
+            flame: args.get(2).filter(|arg| !arg.starts_with("--")).map(|arg| arg.as_str()).unwrap_or(DEFAULT_FLAME).to_string(),
+            gamma: gamma,
+            supersample: supersample
+        }
+    }
+}
+
+/// # `palette`
+/// Gets the colour of a colour index in the range [0, 1]
+pub fn palette(index: f32) -> u32 {
+    let position = index.clamp(0.0, 1.0) * (PALETTE.len() - 1) as f32;
+    let from = (position as usize).min(PALETTE.len() - 2);
+    colours::gradient(PALETTE[from], PALETTE[from + 1], position - from as f32)
+}
+
+/// # `Flame`
+/// Structure that renders a fractal flame. A point and a colour index jump around by randomly picked transforms
+/// and every landing is counted in a supersampled histogram, which is shown with log density and gamma correction
+pub struct Flame {
+    visualizer: Visualizer,
+    viewport: Viewport, // Maps the plane of the flame to the cells of the histogram
+    system: FlameSystem,
+    name: String,
+    gamma: f32,
+    random: Random,
+    histogram: Histogram,
+    point: Vector2,
+    colour: f32, // Colour index of the point
+    points: u64
+}
+
+impl Flame {
+    /// # `new`
+    /// Initializes the flame renderer with the given `Settings`
+    pub fn new(settings: Settings) -> Flame {
+        let system = match FlameSystem::preset(&settings.flame) {
+            Some(system) => system,
+            None => FlameSystem::load(&settings.flame)
+                .unwrap_or_else(|err| panic!("\"{}\" is neither a preset ({}) nor a loadable flame file: {}", settings.flame, PRESETS.join(", "), err))
+        };
+
+        let mut flame = Flame {
+            visualizer: Visualizer::new(None),
+            viewport: Viewport::new(Vector2::new(0.0, 0.0), 1.0, Vector2::new(WIDTH, HEIGHT) * settings.supersample as f32),
+            system: system,
+            name: settings.flame,
+            gamma: settings.gamma,
+            random: Random::from_time(),
+            histogram: Histogram::new(U_WIDTH, U_HEIGHT, settings.supersample),
+            point: Vector2::new(0.0, 0.0),
+            colour: 0.0,
+            points: 0
+        };
+        flame.fit();
+        flame
+    }
+
+    /// # `restart`
+    /// Moves the point to a random spot and lets it settle onto the flame
+    fn restart(&mut self) {
+        self.point = Vector2::new(self.random.range(-1.0, 1.0), self.random.range(-1.0, 1.0));
+        self.colour = self.random.next_f32();
+        for _ in 0..SETTLE_ITERATIONS {
+            self.step();
+        }
+    }
+
+    /// # `step`
+    /// Applies a random transform to the point, its colour index moves halfway to the colour of the transform
+    fn step(&mut self) {
+        let transform = self.system.choose(&mut self.random);
+        self.point = transform.apply(self.point, &mut self.random);
+        self.colour = (self.colour + transform.colour) / 2.0;
+    }
+
+    /// # `fit`
+    /// Centres the view on the flame and zooms so that all but the farthest points fit in the window
+    fn fit(&mut self) {
+        self.restart();
+        let (mut xs, mut ys) = (Vec::with_capacity(FIT_SAMPLES), Vec::with_capacity(FIT_SAMPLES));
+        for _ in 0..FIT_SAMPLES {
+            self.step();
+            if self.point.x.is_finite() && self.point.y.is_finite() {
+                xs.push(self.point.x);
+                ys.push(self.point.y);
+            }
+            else {
+                self.restart();
+            }
+        }
+
+        if xs.is_empty() {
+            panic!("The flame \"{}\" has no finite points", self.name)
+        }
+
+        let range = |values: &mut Vec<f32>| {
+            values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            let quantile = |q: f32| values[((values.len() - 1) as f32 * q) as usize];
+            (quantile(FIT_QUANTILE), quantile(1.0 - FIT_QUANTILE))
+        };
+        let ((min_x, max_x), (min_y, max_y)) = (range(&mut xs), range(&mut ys));
+
+        self.viewport.centre = Vector2::new(min_x + max_x, min_y + max_y) / 2.0;
+        self.viewport.scale = (max_x - min_x).max((max_y - min_y) * WIDTH / HEIGHT).max(f32::EPSILON) * MARGIN;
+    }
+
+    /// # `sample`
+    /// Moves the point `count` times and adds every landing to the histogram
+    pub fn sample(&mut self, count: usize) {
+        let screen = match self.viewport.screen_map() {
+            Some(screen) => screen,
+            None => return
+        };
+        for _ in 0..count {
+            self.step();
+            if !self.point.x.is_finite() || !self.point.y.is_finite() {
+                self.restart();
+                continue;
+            }
+
+            let cell = screen.world_to_pixel(self.point);
+            if cell.x >= 0.0 && cell.y >= 0.0 && cell.x < self.viewport.screen.x && cell.y < self.viewport.screen.y {
+                self.histogram.add(cell.x as usize + cell.y as usize * self.viewport.screen.x as usize, palette(self.colour));
+            }
+        }
+        self.points += count as u64;
+    }
+
+    /// # `draw`
+    /// Keeps sampling and refining the image until the window is closed
+    pub fn draw(&mut self) {
+        self.restart();
+
+        while self.visualizer.window.is_open() {
+            self.sample(POINTS_PER_FRAME);
+
+            self.visualizer.buffer = self.histogram.render(self.gamma);
+            self.visualizer.window.set_title(&format!("Flame - {} - {} points", self.name, self.points));
+            self.visualizer.apply_buffer();
+        }
+    }
+}
+
+// === SUB MODS ===
+
+pub mod system;
+pub mod variations;
+pub mod histogram;
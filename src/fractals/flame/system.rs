@@ -0,0 +1,127 @@
+use crate::math::{
+    vector::Vector2,
+    random::Random
+};
+use crate::fractals::ifs::system::AffineMap;
+
+use super::variations::Variation;
+
+use std::fs::File;
+use std::io::{Error, ErrorKind, Read};
+
+// Presets, written in the flame file format (see `FlameSystem::load`)
+const SIERPINSKI : &str = "
+1 0.0  0.5 0 0 0.5  -0.5 -0.5  linear 1
+1 0.5  0.5 0 0 0.5  0.5  -0.5  linear 1
+1 1.0  0.5 0 0 0.5  0    0.5   linear 1
+0.3 0.8  -0.6 0 0 -0.6  0 0  spherical 1
+";
+const SWIRL : &str = "
+1   0.0  0.56 -0.31 0.31 0.56  0.3 0    swirl 0.8 linear 0.2
+1   0.6  -0.6 0.2 -0.2 -0.6   -0.5 0.2  spherical 0.7 sinusoidal 0.3
+0.5 1.0  0.4 0 0 0.4  0 -0.6  julia 1
+";
+const JULIA : &str = "
+1 0.1  0.6 0.25 -0.25 0.6  0.2 0.1    julia 1
+1 0.9  -0.4 0.5 -0.5 -0.4  -0.3 -0.2  julia 0.7 disc 0.3
+";
+pub const PRESETS : [&str; 3] = ["sierpinski", "swirl", "julia"];
+
+/// # `Transform`
+/// One function of a flame, an affine map (with the weight it is picked with) followed by a weighted sum of variations.
+/// The colour index in the range [0, 1] is blended into the colour of every point the transform is applied to
+#[derive(Clone, Debug, PartialEq)]
+pub struct Transform {
+    pub map: AffineMap,
+    pub variations: Vec<(Variation, f32)>,
+    pub colour: f32
+}
+
+impl Transform {
+    /// # `apply`
+    /// Applies the affine map and then the variations to the point
+    pub fn apply(&self, point: Vector2, random: &mut Random) -> Vector2 {
+        let point = self.map.apply(point);
+        self.variations.iter()
+            .fold(Vector2::new(0.0, 0.0), |sum, (variation, weight)| sum + *weight * variation.apply(point, random))
+    }
+}
+
+/// # `FlameSystem`
+/// The transforms of a fractal flame
+#[derive(Clone, Debug, PartialEq)]
+pub struct FlameSystem {
+    pub transforms: Vec<Transform>
+}
+
+impl FlameSystem {
+    /// # `preset`
+    /// Gets the preset with the given name (`sierpinski`, `swirl` or `julia`), `None` if there is no such preset
+    pub fn preset(name: &str) -> Option<FlameSystem> {
+        let text = match name.to_lowercase().trim() {
+            "sierpinski" => SIERPINSKI,
+            "swirl" => SWIRL,
+            "julia" => JULIA,
+            _ => return None
+        };
+        Some(FlameSystem::parse(text, name).unwrap())
+    }
+
+    /// # `load`
+    /// Loads a flame from a file. Every line is a transform written as `weight colour a b c d e f variation weight [variation weight ...]`
+    /// where the affine map is (x, y) -> (ax + by + e, cx + dy + f) and the variations are given by name, such as `spherical 0.5`.
+    /// Empty lines and lines starting with '#' are skipped. Returns an error if the file can not be read or a line is invalid
+    pub fn load(path: &str) -> std::io::Result<FlameSystem> {
+        let mut text = String::new();
+        File::open(path)?.read_to_string(&mut text)?;
+        FlameSystem::parse(&text, path)
+    }
+
+    /// # `parse`
+    /// Reads the transforms of a flame from the text of a flame file, `source` is the name of the file used in the errors
+    fn parse(text: &str, source: &str) -> std::io::Result<FlameSystem> {
+        let mut transforms = Vec::new();
+        for (number, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let invalid = |message: &str| Error::new(ErrorKind::InvalidData, format!("\"{}\" line {}: {}", source, number + 1, message));
+            let fields : Vec<&str> = line.split_whitespace().collect();
+            if fields.len() < 10 || !fields.len().is_multiple_of(2) {
+                return Err(invalid("expected weight, colour, a b c d e f and pairs of variation and weight"));
+            }
+
+            let number = |field: &str| field.parse::<f32>().map_err(|_| invalid("invalid number"));
+            let values = fields[0..8].iter().map(|field| number(field)).collect::<std::io::Result<Vec<f32>>>()?;
+            if values[0] < 0.0 {
+                return Err(invalid("the weight can not be negative"));
+            }
+
+            let variations = fields[8..].chunks_exact(2)
+                .map(|pair| {
+                    let variation = Variation::from_name(pair[0]).ok_or_else(|| invalid("unknown variation"))?;
+                    Ok((variation, number(pair[1])?))
+                })
+                .collect::<std::io::Result<Vec<(Variation, f32)>>>()?;
+
+            transforms.push(Transform {
+                map: AffineMap::from_coefficients([values[2], values[3], values[4], values[5], values[6], values[7]], values[0]),
+                variations: variations,
+                colour: values[1].clamp(0.0, 1.0)
+            });
+        }
+
+        if transforms.iter().map(|transform| transform.map.weight).sum::<f32>() <= 0.0 {
+            return Err(Error::new(ErrorKind::InvalidData, format!("\"{}\" has no transforms with a positive weight", source)));
+        }
+        Ok(FlameSystem {transforms: transforms})
+    }
+
+    /// # `choose`
+    /// Picks a random transform, each with a probability proportional to its weight
+    pub fn choose(&self, random: &mut Random) -> &Transform {
+        random.pick(&self.transforms, |transform| transform.map.weight)
+    }
+}
@@ -0,0 +1,77 @@
+use crate::math::{
+    vector::Vector2,
+    random::Random
+};
+
+use std::f32::consts::PI;
+
+/// # `Variation`
+/// The nonlinear functions a flame transform bends the plane with, after its affine map.
+/// With r the distance to the origin and θ = atan2(x, y) (the angle from the y axis, as in the original flame paper)
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Variation {
+    Linear, // (x, y)
+    Sinusoidal, // (sin x, sin y)
+    Spherical, // (x, y) / r^2
+    Swirl, // Rotation by r^2
+    Horseshoe, // ((x - y)(x + y), 2xy) / r
+    Polar, // (θ / π, r - 1)
+    Handkerchief, // r (sin(θ + r), cos(θ - r))
+    Heart, // r (sin(θr), -cos(θr))
+    Disc, // θ / π (sin(πr), cos(πr))
+    Spiral, // (cos θ + sin r, sin θ - cos r) / r
+    Hyperbolic, // (sin θ / r, r cos θ)
+    Julia // √r (cos(θ / 2 + Ω), sin(θ / 2 + Ω)) where Ω is randomly 0 or π
+}
+
+impl Variation {
+    /// # `from_name`
+    /// Gets the variation with the given name, such as `spherical`. `None` if there is no such variation
+    pub fn from_name(name: &str) -> Option<Variation> {
+        match name.to_lowercase().trim() {
+            "linear" => Some(Variation::Linear),
+            "sinusoidal" => Some(Variation::Sinusoidal),
+            "spherical" => Some(Variation::Spherical),
+            "swirl" => Some(Variation::Swirl),
+            "horseshoe" => Some(Variation::Horseshoe),
+            "polar" => Some(Variation::Polar),
+            "handkerchief" => Some(Variation::Handkerchief),
+            "heart" => Some(Variation::Heart),
+            "disc" => Some(Variation::Disc),
+            "spiral" => Some(Variation::Spiral),
+            "hyperbolic" => Some(Variation::Hyperbolic),
+            "julia" => Some(Variation::Julia),
+            _ => None
+        }
+    }
+
+    /// # `apply`
+    /// Applies the variation to the point `p`, the `random` generator is used by the variations that pick between branches
+    pub fn apply(&self, p: Vector2, random: &mut Random) -> Vector2 {
+        let r = p.norm();
+        let theta = p.x.atan2(p.y);
+        // Keeps the variations that divide by r finite at the origin
+        let safe_r = r.max(f32::EPSILON);
+
+        match self {
+            Variation::Linear => p,
+            Variation::Sinusoidal => Vector2::new(p.x.sin(), p.y.sin()),
+            Variation::Spherical => p / (safe_r * safe_r),
+            Variation::Swirl => {
+                let (sin, cos) = (r * r).sin_cos();
+                Vector2::new(p.x * sin - p.y * cos, p.x * cos + p.y * sin)
+            },
+            Variation::Horseshoe => Vector2::new((p.x - p.y) * (p.x + p.y), 2.0 * p.x * p.y) / safe_r,
+            Variation::Polar => Vector2::new(theta / PI, r - 1.0),
+            Variation::Handkerchief => r * Vector2::new((theta + r).sin(), (theta - r).cos()),
+            Variation::Heart => r * Vector2::new((theta * r).sin(), -(theta * r).cos()),
+            Variation::Disc => theta / PI * Vector2::new((PI * r).sin(), (PI * r).cos()),
+            Variation::Spiral => Vector2::new(theta.cos() + r.sin(), theta.sin() - r.cos()) / safe_r,
+            Variation::Hyperbolic => Vector2::new(theta.sin() / safe_r, r * theta.cos()),
+            Variation::Julia => {
+                let omega = if random.next_u64() & 1 == 0 { 0.0 } else { PI };
+                r.sqrt() * Vector2::new((theta / 2.0 + omega).cos(), (theta / 2.0 + omega).sin())
+            }
+        }
+    }
+}
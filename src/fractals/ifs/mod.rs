@@ -0,0 +1,170 @@
+use crate::visuals::{
+    visualizer::{Visualizer, U_WIDTH, U_HEIGHT, HEIGHT, WIDTH},
+    colours};
+use crate::math::{
+    vector::Vector2,
+    viewport::{Viewport, ScreenMap},
+    random::Random
+};
+
+use system::{FunctionSystem, PRESETS};
+
+// Consts
+pub const SETTLE_ITERATIONS : usize = 20; // Chaos game points skipped after a (re)start, they are still on their way to the attractor
+const DEFAULT_SYSTEM : &str = "fern";
+const POINTS_PER_FRAME : usize = 50000;
+const BOUNDS_SAMPLES : usize = 100000; // Chaos game points used to find the extent of the attractor
+const MARGIN : f32 = 1.1; // How much larger than the attractor the view is
+const MAX_SHAPES : usize = 200000; // Most shapes drawn by the deterministic iteration, decides how many levels are drawn
+const MAX_LEVELS : usize = 20; // Levels drawn by the deterministic iteration when there are too few maps to reach MAX_SHAPES
+
+/// # `Ifs`
+/// Structure that visualizes the attractor of an iterated function system, either with the chaos game
+/// (one point jumping around by randomly picked maps, refined over time) or deterministically by applying every map to a shape over and over
+pub struct Ifs {
+    visualizer: Visualizer,
+    viewport: Viewport, // Maps the plane of the system (y pointing up) to the screen
+    system: FunctionSystem,
+    name: String,
+    random: Random,
+    deterministic: bool,
+    hits: Vec<u32>, // Chaos game points that landed in every pixel
+    points: u64
+}
+
+impl Ifs {
+    /// # `new`
+    /// Initializes the IFS visualizer by taking the name of a preset or the path of a system file `Option<&str>` (`None` is for the fern)
+    /// and whether to use the deterministic iteration instead of the chaos game `bool`
+    pub fn new(system: Option<&str>, deterministic: bool) -> Ifs {
+        let name = system.unwrap_or(DEFAULT_SYSTEM);
+        let system = match FunctionSystem::preset(name) {
+            Some(system) => system,
+            None => FunctionSystem::load(name)
+                .unwrap_or_else(|err| panic!("\"{}\" is neither a preset ({}) nor a loadable IFS file: {}", name, PRESETS.join(", "), err))
+        };
+
+        // Fit the attractor in the window, the view is flipped so y points up
+        let mut random = Random::from_time();
+        let (min, max) = system.bounds(&mut random, BOUNDS_SAMPLES);
+        let size = max - min;
+        let centre = (min + max) / 2.0;
+
+        Ifs {
+            visualizer: Visualizer::new(if deterministic { Some(300) } else { None }),
+            viewport: Viewport::new(Vector2::new(centre.x, -centre.y), size.x.max(size.y * WIDTH / HEIGHT).max(f32::EPSILON) * MARGIN, Vector2::new(WIDTH, HEIGHT)),
+            system: system,
+            name: name.to_string(),
+            random: random,
+            deterministic: deterministic,
+            hits: vec![0; U_WIDTH * U_HEIGHT],
+            points: 0
+        }
+    }
+
+    /// # `to_screen`
+    /// Maps a point of the plane of the system to its position on the screen
+    fn to_screen(screen: &ScreenMap, point: Vector2) -> Vector2 {
+        screen.world_to_pixel(Vector2::new(point.x, -point.y))
+    }
+
+    /// # `to_pixel`
+    /// Maps a point of the plane of the system to the index of its pixel in the window, `None` if it is outside of the window
+    fn to_pixel(screen: &ScreenMap, point: Vector2) -> Option<usize> {
+        let pixel = Ifs::to_screen(screen, point);
+
+        if pixel.x < 0.0 || pixel.y < 0.0 || pixel.x >= WIDTH || pixel.y >= HEIGHT {
+            return None;
+        }
+        Some(pixel.x as usize + pixel.y as usize * U_WIDTH)
+    }
+
+    /// # `chaos_game`
+    /// Keeps jumping a point around by randomly picked maps and counts where it lands, until the window is closed
+    fn chaos_game(&mut self) {
+        let mut point = Vector2::new(0.0, 0.0);
+        for _ in 0..SETTLE_ITERATIONS {
+            point = self.system.choose(&mut self.random).apply(point);
+        }
+
+        // The view is never empty, its size is kept above 0 when fitting the attractor
+        let screen = self.viewport.screen_map().unwrap();
+        while self.visualizer.window.is_open() {
+            for _ in 0..POINTS_PER_FRAME {
+                point = self.system.choose(&mut self.random).apply(point);
+                if let Some(idx) = Ifs::to_pixel(&screen, point) {
+                    self.hits[idx] = self.hits[idx].saturating_add(1);
+                }
+            }
+            self.points += POINTS_PER_FRAME as u64;
+
+            self.tone_map();
+            self.visualizer.window.set_title(&format!("IFS - {} - {} points", self.name, self.points));
+            self.visualizer.apply_buffer();
+        }
+    }
+
+    /// # `tone_map`
+    /// Converts the hit counts into colours, on a log scale so that rarely visited parts of the attractor still show
+    fn tone_map(&mut self) {
+        let max = (*self.hits.iter().max().unwrap_or(&0) as f32 + 1.0).ln();
+
+        for (pixel, hits) in self.visualizer.buffer.iter_mut().zip(self.hits.iter()) {
+            *pixel = colours::gradient(colours::BLACK, colours::GREEN, (*hits as f32 + 1.0).ln() / max);
+        }
+    }
+
+    /// # `iterate_shapes`
+    /// Starts from the bounding box of the attractor and replaces every shape by its image under each of the maps,
+    /// drawing every level. The shapes close in on the attractor with every level
+    fn iterate_shapes(&mut self) {
+        let half = self.viewport.scale / MARGIN / 2.0;
+        let (centre, ratio) = (Vector2::new(self.viewport.centre.x, -self.viewport.centre.y), HEIGHT / WIDTH);
+        let mut shapes : Vec<[Vector2; 4]> = vec![[
+            centre + Vector2::new(-half, -half * ratio),
+            centre + Vector2::new(half, -half * ratio),
+            centre + Vector2::new(half, half * ratio),
+            centre + Vector2::new(-half, half * ratio)]];
+
+        // Every level has as many times more shapes as there are maps
+        let levels = ((MAX_SHAPES as f32).ln() / (self.system.maps.len() as f32).ln()).floor().min(MAX_LEVELS as f32) as usize;
+
+        self.visualizer.apply_buffer();
+        self.visualizer.left_pressed();
+
+        let screen = self.viewport.screen_map().unwrap();
+
+        for level in 0..=levels {
+            self.visualizer.clear(None);
+            for shape in shapes.iter() {
+                for corner in 0..shape.len() {
+                    let (from, to) = (Ifs::to_screen(&screen, shape[corner]), Ifs::to_screen(&screen, shape[(corner + 1) % shape.len()]));
+                    let _ = self.visualizer.draw_line(from, to, colours::GREEN, 1);
+                }
+            }
+            self.visualizer.window.set_title(&format!("IFS - {} - level {} of {}", self.name, level, levels));
+            self.visualizer.apply_buffer();
+
+            if level < levels {
+                shapes = shapes.iter()
+                    .flat_map(|shape| self.system.maps.iter().map(move |map| shape.map(|corner| map.apply(corner))))
+                    .collect();
+            }
+        }
+
+        self.visualizer.end();
+    }
+
+    /// # `draw`
+    /// Draws the fractal on the screen
+    pub fn draw(&mut self) {
+        match self.deterministic {
+            true => self.iterate_shapes(),
+            false => self.chaos_game()
+        }
+    }
+}
+
+// === SUB MODS ===
+
+pub mod system;
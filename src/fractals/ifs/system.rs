@@ -0,0 +1,163 @@
+use crate::math::{
+    matrix::Matrix2,
+    vector::Vector2,
+    random::Random,
+    transform::Transform2
+};
+
+use super::SETTLE_ITERATIONS;
+
+use std::fs::File;
+use std::io::{Error, ErrorKind, Read};
+
+// Consts
+const MIN_WEIGHT : f32 = 0.01; // Weight given to maps without one that squash everything onto a line (determinant 0)
+
+// Presets, one map per row written as `a b c d e f weight` (see `FunctionSystem::load`)
+const FERN : [[f32; 7]; 4] = [
+    [0.0, 0.0, 0.0, 0.16, 0.0, 0.0, 0.01], // Stem
+    [0.85, 0.04, -0.04, 0.85, 0.0, 1.6, 0.85], // Smaller copy of the whole fern
+    [0.2, -0.26, 0.23, 0.22, 0.0, 1.6, 0.07], // Left leaf
+    [-0.15, 0.28, 0.26, 0.24, 0.0, 0.44, 0.07] // Right leaf
+];
+const SIERPINSKI : [[f32; 7]; 3] = [
+    [0.5, 0.0, 0.0, 0.5, 0.0, 0.0, 1.0],
+    [0.5, 0.0, 0.0, 0.5, 0.5, 0.0, 1.0],
+    [0.5, 0.0, 0.0, 0.5, 0.25, 0.4330127, 1.0]
+];
+const DRAGON : [[f32; 7]; 2] = [
+    [0.5, -0.5, 0.5, 0.5, 0.0, 0.0, 1.0],
+    [-0.5, -0.5, 0.5, -0.5, 1.0, 0.0, 1.0]
+];
+const MAPLE : [[f32; 7]; 4] = [
+    [0.14, 0.01, 0.0, 0.51, -0.08, -1.31, 0.10],
+    [0.43, 0.52, -0.45, 0.5, 1.49, -0.75, 0.35],
+    [0.45, -0.49, 0.47, 0.47, -1.62, -0.74, 0.35],
+    [0.49, 0.0, 0.0, 0.51, 0.02, 1.62, 0.20]
+];
+const LEVY : [[f32; 7]; 2] = [
+    [0.5, -0.5, 0.5, 0.5, 0.0, 0.0, 1.0],
+    [0.5, 0.5, -0.5, 0.5, 0.5, 0.5, 1.0]
+];
+pub const PRESETS : [&str; 5] = ["fern", "sierpinski", "dragon", "maple", "levy"];
+
+/// # `AffineMap`
+/// An affine transform along with the probability weight it is picked with in the chaos game
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct AffineMap {
+    pub transform: Transform2,
+    pub weight: f32
+}
+
+impl AffineMap {
+    /// # `from_coefficients`
+    /// Creates the map (x, y) -> (ax + by + e, cx + dy + f) from `[a, b, c, d, e, f]` with the given `weight`
+    pub fn from_coefficients(coefficients: [f32; 6], weight: f32) -> AffineMap {
+        let [a, b, c, d, e, f] = coefficients;
+        AffineMap {
+            transform: Transform2::new(Matrix2 {c11: a, c12: b, c21: c, c22: d}, Vector2::new(e, f)),
+            weight: weight
+        }
+    }
+
+    /// # `apply`
+    /// Maps the given point
+    pub fn apply(&self, point: Vector2) -> Vector2 {
+        self.transform.transform_point(point)
+    }
+}
+
+/// # `FunctionSystem`
+/// An iterated function system, a set of contracting affine maps whose attractor is the fractal
+#[derive(Clone, Debug, PartialEq)]
+pub struct FunctionSystem {
+    pub maps: Vec<AffineMap>
+}
+
+impl FunctionSystem {
+    /// # `from_rows`
+    /// Creates a system from rows written as `a b c d e f weight`
+    fn from_rows(rows: &[[f32; 7]]) -> FunctionSystem {
+        FunctionSystem {
+            maps: rows.iter()
+                .map(|row| AffineMap::from_coefficients([row[0], row[1], row[2], row[3], row[4], row[5]], row[6]))
+                .collect()
+        }
+    }
+
+    /// # `preset`
+    /// Gets the preset with the given name (`fern`, `sierpinski`, `dragon`, `maple` or `levy`), `None` if there is no such preset
+    pub fn preset(name: &str) -> Option<FunctionSystem> {
+        match name.to_lowercase().trim() {
+            "fern" => Some(FunctionSystem::from_rows(&FERN)),
+            "sierpinski" => Some(FunctionSystem::from_rows(&SIERPINSKI)),
+            "dragon" => Some(FunctionSystem::from_rows(&DRAGON)),
+            "maple" => Some(FunctionSystem::from_rows(&MAPLE)),
+            "levy" => Some(FunctionSystem::from_rows(&LEVY)),
+            _ => None
+        }
+    }
+
+    /// # `load`
+    /// Loads a system from a file. Every line is a map written as `a b c d e f [weight]`, standing for
+    /// (x, y) -> (ax + by + e, cx + dy + f). Maps without a weight are weighted by how much they shrink the area (|ad - bc|).
+    /// Empty lines and lines starting with '#' are skipped. Returns an error if the file can not be read or a line is invalid
+    pub fn load(path: &str) -> std::io::Result<FunctionSystem> {
+        let mut text = String::new();
+        File::open(path)?.read_to_string(&mut text)?;
+
+        let mut maps = Vec::new();
+        for (number, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let invalid = |message: &str| Error::new(ErrorKind::InvalidData, format!("\"{}\" line {}: {}", path, number + 1, message));
+            let values = line.split_whitespace()
+                .map(|field| field.parse::<f32>().map_err(|_| invalid("invalid number")))
+                .collect::<std::io::Result<Vec<f32>>>()?;
+            if values.len() != 6 && values.len() != 7 {
+                return Err(invalid("expected a b c d e f and an optional weight"));
+            }
+
+            let mut map = AffineMap::from_coefficients([values[0], values[1], values[2], values[3], values[4], values[5]], 0.0);
+            map.weight = match values.get(6) {
+                Some(weight) if *weight < 0.0 => return Err(invalid("the weight can not be negative")),
+                Some(weight) => *weight,
+                None => map.transform.matrix.determinant().abs().max(MIN_WEIGHT)
+            };
+            maps.push(map);
+        }
+
+        if maps.iter().map(|map| map.weight).sum::<f32>() <= 0.0 {
+            return Err(Error::new(ErrorKind::InvalidData, format!("\"{}\" has no maps with a positive weight", path)));
+        }
+        Ok(FunctionSystem {maps: maps})
+    }
+
+    /// # `choose`
+    /// Picks a random map, each with a probability proportional to its weight
+    pub fn choose(&self, random: &mut Random) -> &AffineMap {
+        random.pick(&self.maps, |map| map.weight)
+    }
+
+    /// # `bounds`
+    /// Estimates the bounding box of the attractor with the chaos game, returns the (minimum, maximum) corners
+    pub fn bounds(&self, random: &mut Random, samples: usize) -> (Vector2, Vector2) {
+        let mut point = Vector2::new(0.0, 0.0);
+        let mut min = Vector2::new(f32::INFINITY, f32::INFINITY);
+        let mut max = Vector2::new(f32::NEG_INFINITY, f32::NEG_INFINITY);
+
+        for n in 0..samples {
+            point = self.choose(random).apply(point);
+
+            if n >= SETTLE_ITERATIONS {
+                min = Vector2::new(min.x.min(point.x), min.y.min(point.y));
+                max = Vector2::new(max.x.max(point.x), max.y.max(point.y));
+            }
+        }
+
+        (min, max)
+    }
+}
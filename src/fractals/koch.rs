@@ -6,7 +6,8 @@ use super::super::visuals::{
 
 use super::super::math::{
     matrix::Matrix2,
-    vector::Vector2
+    vector::Vector2,
+    transform::Transform2
 };
 
 // Consts
@@ -28,6 +29,15 @@ impl KochLine {
         visualizer.draw_line(self.start, self.end, colours::RUST, 1);
     }
 
+    /// # `frame`
+    /// Returns the transform that takes the unit line from (0, 0) to (1, 0) onto this line
+    fn frame(&self) -> Transform2 {
+        let vector = self.end - self.start;
+        // Rotates and scales (1, 0) onto the vector
+        let matrix = Matrix2 {c11: vector.x, c12: -vector.y, c21: vector.y, c22: vector.x};
+        Transform2::new(matrix, self.start)
+    }
+
     // === GET POINTS OF THE DIVIDED KOCH LINES ===
 
     pub fn koch_a(&self) -> Vector2 {
@@ -35,21 +45,17 @@ impl KochLine {
     }
 
     pub fn koch_b(&self) -> Vector2 {
-        let vector = self.end - self.start;
-        self.start + vector / 3.0
+        self.frame().transform_point(Vector2::new(1.0 / 3.0, 0.0))
     }
 
     pub fn koch_c(&self) -> Vector2 {
-        let rot_matrix = Matrix2::rotation(ANGLE);
-        let vector = (self.end - self.start) / 3.0;
-        let vector_rotated = rot_matrix * vector; 
-
-        vector + vector_rotated + self.start
+        // A third of the line rotated by ANGLE, starting a third of the way along
+        let peak = Transform2::translation(Vector2::new(1.0 / 3.0, 0.0)) * Transform2::rotation(ANGLE);
+        (self.frame() * peak).transform_point(Vector2::new(1.0 / 3.0, 0.0))
     }
 
     pub fn koch_d(&self) -> Vector2 {
-        let vector = self.end - self.start;
-        self.start + vector * (2.0 / 3.0)
+        self.frame().transform_point(Vector2::new(2.0 / 3.0, 0.0))
     }
 
     pub fn koch_e(&self) -> Vector2 {
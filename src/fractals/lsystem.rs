@@ -0,0 +1,246 @@
+use crate::visuals::{
+    visualizer::{Visualizer, WIDTH, HEIGHT},
+    colours};
+use crate::math::{
+    vector::Vector2,
+    transform::Transform2,
+    lsystem::LSystem,
+    random::Random,
+    turtle::Turtle
+};
+use crate::cli;
+
+// Presets, written in the definition file format (see `LSystem::parse`)
+const KOCH : &str = "
+axiom F--F--F
+rule F -> F+F--F+F
+angle 60
+iterations 4
+heading 0
+";
+const QUADRATIC_KOCH : &str = "
+axiom F-F-F-F
+rule F -> F-F+F+FF-F-F+F
+angle 90
+iterations 3
+heading 0
+";
+const DRAGON : &str = "
+axiom FX
+rule X -> X+YF+
+rule Y -> -FX-Y
+angle 90
+iterations 12
+heading 0
+";
+const ARROWHEAD : &str = "
+axiom YF
+rule X -> YF+XF+Y
+rule Y -> XF-YF-X
+angle 60
+iterations 7
+heading 0
+";
+const HILBERT : &str = "
+axiom A
+rule A -> +BF-AFA-FB+
+rule B -> -AF+BFB+FA-
+angle 90
+iterations 6
+heading 0
+";
+const GOSPER : &str = "
+axiom FX
+rule X -> FX-FY--FY+FX++FXFX+FY-
+rule Y -> +FX-FYFY--FY-FX++FX+FY
+rule F ->
+angle 60
+iterations 4
+heading 0
+";
+const PENROSE : &str = "
+axiom [N]++[N]++[N]++[N]++[N]
+rule M -> OF++PF----NF[-OF----MF]++
+rule N -> +OF--PF[---MF--NF]+
+rule O -> -MF++NF[+++OF++PF]-
+rule P -> --OF++++MF[+PF++++NF]--NF
+rule F ->
+angle 36
+iterations 5
+heading 0
+";
+const PLANT : &str = "
+axiom X
+rule X -> F-[[X]+X]+F[+FX]-X
+rule F -> FF
+angle 22.5
+iterations 5
+";
+const WEED : &str = "
+axiom F
+rule F -> F[+F]F[-F]F
+angle 25.7
+iterations 5
+";
+const TWIG : &str = "
+axiom X
+rule X -> F[+X]F[-X]+X
+rule F -> FF
+angle 20
+iterations 7
+";
+const BUSH : &str = "
+axiom F
+rule F -> FF-[-F+F+F]+[+F-F-F]
+angle 22.5
+iterations 4
+";
+const SHRUB : &str = "
+axiom F
+rule F -> F[+F]F[-F]F : 0.33
+rule F -> F[+F]F : 0.33
+rule F -> F[-F]F : 0.34
+angle 25.7
+iterations 5
+";
+const SIGNAL : &str = "
+# Signals travel along the branches through the contexts (Hogeweg and Hesper)
+axiom F1F1F1
+ignore +-F
+rule 0 < 0 > 0 -> 0
+rule 0 < 0 > 1 -> 1[+F1F1]
+rule 0 < 1 > 0 -> 1
+rule 0 < 1 > 1 -> 1
+rule 1 < 0 > 0 -> 0
+rule 1 < 0 > 1 -> 1F1
+rule 1 < 1 > 0 -> 0
+rule 1 < 1 > 1 -> 0
+rule * < + > * -> -
+rule * < - > * -> +
+angle 22.5
+iterations 30
+";
+const BRANCHING : &str = "
+# The branches get shorter and narrower until they are shorter than 1
+axiom A(20)
+rule A(s) : s >= 1 -> !(s/4)F(s)[+A(s/1.5)][-A(s/1.5)]
+angle 30
+iterations 10
+";
+pub const PRESETS : [&str; 14] = ["koch", "quadratic_koch", "dragon", "arrowhead", "hilbert", "gosper", "penrose", "plant", "weed", "twig", "bush", "shrub", "signal", "branching"];
+
+const DEFAULT_PRESET : &str = "plant";
+const MARGIN : f32 = 1.1; // How much larger than the drawing the view is
+const PALETTE : [u32; 5] = [colours::WHITE, colours::GREEN, colours::GOLD, colours::CERISE, colours::BLUE]; // Colours picked by the colour index, in a cycle
+
+/// # `preset`
+/// Gets the preset L-System with the given name (one of `PRESETS`), `None` if there is no such preset
+pub fn preset(name: &str) -> Option<LSystem> {
+    let text = match name.to_lowercase().trim() {
+        "koch" => KOCH,
+        "quadratic_koch" => QUADRATIC_KOCH,
+        "dragon" => DRAGON,
+        "arrowhead" => ARROWHEAD,
+        "hilbert" => HILBERT,
+        "gosper" => GOSPER,
+        "penrose" => PENROSE,
+        "plant" => PLANT,
+        "weed" => WEED,
+        "twig" => TWIG,
+        "bush" => BUSH,
+        "shrub" => SHRUB,
+        "signal" => SIGNAL,
+        "branching" => BRANCHING,
+        _ => return None
+    };
+    Some(LSystem::parse(text, name).unwrap())
+}
+
+/// # `LSystemFractal`
+/// Structure that draws an L-System from a preset or a definition file with turtle graphics, scaled to fit the window
+pub struct LSystemFractal {
+    visualizer: Visualizer,
+    lsystem: LSystem,
+    name: String,
+    seed: u64 // Seed of the alternatives picked by stochastic rules
+}
+
+impl LSystemFractal {
+    /// # `new`
+    /// Initializes the drawing from the command line arguments, `lsystem [--preset <name>|--file <file>] [--iterations <n>] [--seed <seed>]`
+    pub fn new(args: &[String]) -> LSystemFractal {
+        let (name, mut lsystem) = match cli::flag_value(args, "--file") {
+            Some(path) => (path, LSystem::load(path).unwrap_or_else(|err| panic!("Could not load the L-System: {}", err))),
+            None => {
+                let name = cli::flag_value(args, "--preset").unwrap_or(DEFAULT_PRESET);
+                (name, preset(name).unwrap_or_else(|| panic!("No such preset \"{}\", the presets are {}", name, PRESETS.join(", "))))
+            }
+        };
+
+        if let Some(iterations) = cli::flag_value(args, "--iterations") {
+            lsystem.iterations = iterations.parse::<usize>().unwrap_or_else(|_| panic!("Invalid number of iterations \"{}\"", iterations));
+        }
+
+        // Without a seed every run grows a different plant, the seed is shown in the title to grow the same one again
+        let seed = cli::flag_value(args, "--seed")
+            .map(|seed| seed.parse::<u64>().unwrap_or_else(|_| panic!("Invalid seed \"{}\"", seed)))
+            .unwrap_or_else(|| Random::from_time().next_u64());
+        lsystem.seed(seed);
+
+        LSystemFractal {
+            visualizer: Visualizer::new(None),
+            lsystem: lsystem,
+            name: name.to_string(),
+            seed: seed
+        }
+    }
+
+    /// # `turtle`
+    /// Creates the turtle that walks the sentence, from the origin of its plane
+    fn turtle(&self) -> Turtle {
+        Turtle::new(Vector2::new(0.0, 0.0), self.lsystem.heading, self.lsystem.step, self.lsystem.angle)
+    }
+
+    /// # `fit`
+    /// Returns the transform that maps the plane of the turtle (y pointing up) to the screen so that the box from `min` to `max` fits
+    fn fit(min: Vector2, max: Vector2) -> Transform2 {
+        let size = max - min;
+        let scale = (WIDTH / size.x.max(f32::EPSILON)).min(HEIGHT / size.y.max(f32::EPSILON)) / MARGIN;
+        Transform2::translation(Vector2::new(WIDTH, HEIGHT) / 2.0) * Transform2::scale(scale, -scale) * Transform2::translation(-(min + max) / 2.0)
+    }
+
+    /// # `draw`
+    /// Grows the L-System, draws it and waits until the window is closed. The sentence is streamed into the turtle twice,
+    /// first to find the extent of the drawing and then to draw it, reseeding so both walk the same sentence
+    pub fn draw(&mut self) {
+        let title = format!("L-System - {} - {} iterations", self.name, self.lsystem.iterations);
+        if let Some(count) = self.lsystem.symbol_count() {
+            self.visualizer.window.set_title(&format!("{} - growing {} symbols", title, count));
+        }
+
+        self.lsystem.seed(self.seed);
+        let (mut min, mut max) = (Vector2::new(f32::MAX, f32::MAX), Vector2::new(f32::MIN, f32::MIN));
+        let (mut symbols, mut segments) = (0u64, 0u64);
+        for segment in self.turtle().trace(self.lsystem.stream().inspect(|_| symbols += 1)) {
+            for point in [segment.start, segment.end] {
+                min = Vector2::new(min.x.min(point.x), min.y.min(point.y));
+                max = Vector2::new(max.x.max(point.x), max.y.max(point.y));
+            }
+            segments += 1;
+        }
+
+        if segments > 0 {
+            self.visualizer.apply_transform(LSystemFractal::fit(min, max));
+        }
+        self.lsystem.seed(self.seed);
+        let turtle = self.turtle();
+        for segment in turtle.trace(self.lsystem.stream()) {
+            let thickness = (segment.width.round() as usize).max(1);
+            let _ = self.visualizer.draw_line(segment.start, segment.end, PALETTE[segment.colour % PALETTE.len()], thickness);
+        }
+
+        self.visualizer.window.set_title(&format!("{} - {} symbols, {} segments, seed {}", title, symbols, segments, self.seed));
+        self.visualizer.apply_buffer();
+        self.visualizer.end();
+    }
+}
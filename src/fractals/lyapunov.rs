@@ -0,0 +1,91 @@
+use crate::visuals::{
+    visualizer::{Visualizer, U_WIDTH, U_HEIGHT, HEIGHT, WIDTH},
+    colours};
+use crate::math::{
+    vector::Vector2,
+    viewport::Viewport
+};
+
+// Consts
+const START : f32 = 0.4; // Starting x, not 0.5 which r = 4 maps straight onto the fixed point 0
+const WARMUP : usize = 100; // Iterations ignored before measuring, lets the logistic map settle
+const ITERATIONS : usize = 300;
+const DEFAULT_SEQUENCE : &str = "AB";
+const REGION_CENTRE : Vector2 = Vector2 {x: 3.0, y: 2.75}; // (a, b) at the centre of the window
+const REGION_WIDTH : f32 = 2.0; // Range of a, the range of b follows from the screen ratio (4:3)
+const EXPONENT_LIMIT : f32 = 2.0; // Exponents further from 0 than this get the full gradient colour
+
+/// # `exponent`
+/// Approximates the Lyapunov exponent of the logistic map for the given `a` and `b` parameters,
+/// where r follows the `sequence` (true for a and false for b)
+pub fn exponent(sequence: &[bool], a: f32, b: f32) -> f32 {
+    let mut x = START;
+    let mut sum = 0.0;
+    let mut step = sequence.iter().cycle();
+
+    for n in 0..(WARMUP + ITERATIONS) {
+        let r = if *step.next().unwrap() { a } else { b };
+
+        // The exponent is the average of ln|f'(x)| along the orbit, where f'(x) = r * (1 - 2x) is taken at x before it is mapped
+        if n >= WARMUP {
+            sum += (r * (1.0 - 2.0 * x)).abs().ln();
+        }
+        x = r * x * (1.0 - x);
+    }
+
+    sum / ITERATIONS as f32
+}
+
+/// # `Lyapunov`
+/// Structure that visualizes the Lyapunov fractal of the logistic map x -> r * x * (1 - x)
+/// where r is switched between a and b following a sequence such as "AABAB"
+pub struct Lyapunov {
+    visualizer: Visualizer,
+    viewport: Viewport, // Maps pixels to (a, b)
+    sequence: Vec<bool> // true for A and false for B
+}
+
+impl Lyapunov {
+    /// # `new`
+    /// Initializes the Lyapunov fractal visualizer by taking an optional A/B sequence `Option<&str>`. `None` is for default which is "AB"
+    pub fn new(sequence: Option<&str>) -> Lyapunov {
+        let sequence = sequence.unwrap_or(DEFAULT_SEQUENCE).to_uppercase();
+
+        if sequence.is_empty() || sequence.chars().any(|c| c != 'A' && c != 'B') {
+            panic!("Lyapunov sequence must only consist of A and B, got \"{}\"", sequence)
+        }
+
+        Lyapunov {
+            visualizer: Visualizer::new(None),
+            viewport: Viewport::new(REGION_CENTRE, REGION_WIDTH, Vector2::new(WIDTH, HEIGHT)),
+            sequence: sequence.chars().map(|c| c == 'A').collect()
+        }
+    }
+
+    /// # `draw`
+    /// Draws the fractal on the screen
+    pub fn draw(&mut self) {
+        for px in 0..U_WIDTH {
+            for py in 0..U_HEIGHT {
+                // (a, b) parameters based on the pixel, b grows upwards like a regular plot so the row is flipped before mapping
+                let parameters = self.viewport.pixel_to_world(Vector2::new(px as f32, HEIGHT - 1.0 - py as f32));
+
+                let colour = Lyapunov::get_colour(exponent(&self.sequence, parameters.x, parameters.y));
+                let _ = self.visualizer.set_pixel(Vector2::from_isize((px as isize, py as isize)), colour);
+            }
+        }
+        self.visualizer.apply_buffer();
+
+        self.visualizer.end();
+    }
+
+    /// # `get_colour`
+    /// Gets the colour of the pixel based on the exponent. Negative exponents (stable) are shaded gold while positive exponents (chaotic) are shaded blue
+    fn get_colour(exponent: f32) -> u32 {
+        match exponent {
+            e if e.is_nan() => colours::BLACK,
+            e if e <= 0.0 => colours::gradient(colours::BLACK, colours::GOLD, -e / EXPONENT_LIMIT),
+            e => colours::gradient(colours::NAVY, colours::BLUE, e / EXPONENT_LIMIT)
+        }
+    }
+}
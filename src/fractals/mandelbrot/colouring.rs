@@ -0,0 +1,184 @@
+use crate::visuals::{colours, image::Image};
+use crate::math::vector::Vector2;
+
+use super::kernel::Orbit;
+
+// Consts
+const BAILOUT : f32 = 2.0; // Escape radius of the regular escape time bands
+const LARGE_BAILOUT : f32 = 1000.0; // The distance estimation is only accurate once |z| is large
+const FILAMENT_WIDTH : f32 = 1.0; // Width of the boundary filaments in pixels
+const LIGHT_ANGLE : f32 = std::f32::consts::PI / 4.0; // Direction the light of the slope mode comes from
+const LIGHT_HEIGHT : f32 = 1.5; // Height of the light above the plane, larger is a softer shading
+const TRAP_FALLOFF : f32 = 0.5; // Trap distance at which the trap colour has faded out completely
+const TRAP_IMAGE_SIZE : f32 = 1.0; // Width and height of the trap image in the complex plane, centred on the trap
+const INTERIOR_DISTANCE_RANGE : f32 = 8.0; // Interior distances (in pixels) fade out over this many powers of e
+
+/// # `Colouring`
+/// The ways a pixel can be coloured from its orbit
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Colouring {
+    Bands, // Escape time bands based on the amount of iterations
+    Distance, // Exterior distance estimation, the boundary is drawn as thin filaments
+    Slope, // 3D looking shading based on the direction of the derivative
+    Trap // Closest distance of the orbit to the orbit trap, or the trap image at the closest point
+}
+
+impl Colouring {
+    /// # `from_name`
+    /// Gets the colouring mode with the given name, `None` if there is no such mode
+    pub fn from_name(name: &str) -> Option<Colouring> {
+        match name.to_lowercase().trim() {
+            "bands" => Some(Colouring::Bands),
+            "distance" => Some(Colouring::Distance),
+            "slope" => Some(Colouring::Slope),
+            "trap" => Some(Colouring::Trap),
+            _ => None
+        }
+    }
+
+    /// # `name`
+    /// Gets the name of the colouring mode, the inverse of `from_name`
+    pub fn name(&self) -> &'static str {
+        match self {
+            Colouring::Bands => "bands",
+            Colouring::Distance => "distance",
+            Colouring::Slope => "slope",
+            Colouring::Trap => "trap"
+        }
+    }
+
+    /// # `next`
+    /// Gets the colouring mode that comes after this one, used to cycle through the modes
+    pub fn next(&self) -> Colouring {
+        match self {
+            Colouring::Bands => Colouring::Distance,
+            Colouring::Distance => Colouring::Slope,
+            Colouring::Slope => Colouring::Trap,
+            Colouring::Trap => Colouring::Bands
+        }
+    }
+
+    /// # `bailout`
+    /// Gets the escape radius the orbits have to be iterated with for this mode
+    pub fn bailout(&self) -> f32 {
+        match self {
+            Colouring::Bands => BAILOUT,
+            _ => LARGE_BAILOUT
+        }
+    }
+
+    /// # `colour`
+    /// Gets the colour of a pixel by taking its `Orbit`, the size of a pixel in the complex plane `f32`,
+    /// the offset added to the iterations to cycle the bands `f32` and the image that is placed on the orbit trap, if any
+    pub fn colour(&self, orbit: &Orbit, pixel_size: f32, palette_offset: f32, trap_image: Option<&Image>) -> u32 {
+        match self {
+            Colouring::Bands => (colours::BLACK + (orbit.iterations as f32 + palette_offset).max(0.0) as u32) << 10,
+            Colouring::Distance => {
+                if !orbit.escaped {
+                    return colours::WHITE;
+                }
+
+                // d = 0.5 |z| ln|z| / |dz|, measured in pixels so the filaments stay equally thin at any zoom
+                let norm = orbit.z.norm();
+                let distance = 0.5 * norm * norm.ln() / orbit.dz.norm();
+                colours::gradient(colours::WHITE, colours::BLACK, (distance / (FILAMENT_WIDTH * pixel_size)).sqrt())
+            },
+            Colouring::Slope => {
+                if !orbit.escaped {
+                    return colours::BLACK;
+                }
+
+                // The normal of the "height map" points along z / dz, the shade is how much it faces the light
+                let normal = orbit.z.complex_div(orbit.dz);
+                let normal = normal / normal.norm();
+                let light = Vector2::new(LIGHT_ANGLE.cos(), LIGHT_ANGLE.sin());
+                let shade = (normal.x * light.x + normal.y * light.y + LIGHT_HEIGHT) / (1.0 + LIGHT_HEIGHT);
+                colours::gradient(colours::BLACK, colours::WHITE, shade)
+            },
+            Colouring::Trap => {
+                // Image traps show the image wherever the closest point of the orbit lands on it
+                let texture = Vector2::new(orbit.trap_point.x / TRAP_IMAGE_SIZE + 0.5, orbit.trap_point.y / TRAP_IMAGE_SIZE + 0.5);
+                match trap_image {
+                    Some(image) if texture.x >= 0.0 && texture.x < 1.0 && texture.y >= 0.0 && texture.y < 1.0 => image.sample(texture.x, texture.y),
+                    _ => colours::gradient(colours::GOLD, colours::BLACK, (orbit.trap_distance / TRAP_FALLOFF).sqrt())
+                }
+            }
+        }
+    }
+}
+
+/// # `Interior`
+/// The ways the points inside the set (those that never escape) can be coloured
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Interior {
+    Flat, // Same colour as the exterior mode gives them
+    Magnitude, // Size of the last point of the orbit
+    Angle, // Average angle of the points of the orbit
+    Period, // Length of the cycle the orbit settles in
+    Distance // Interior distance estimation, the distance to the boundary
+}
+
+impl Interior {
+    /// # `from_name`
+    /// Gets the interior colouring mode with the given name, `None` if there is no such mode
+    pub fn from_name(name: &str) -> Option<Interior> {
+        match name.to_lowercase().trim() {
+            "flat" => Some(Interior::Flat),
+            "magnitude" => Some(Interior::Magnitude),
+            "angle" => Some(Interior::Angle),
+            "period" => Some(Interior::Period),
+            "distance" => Some(Interior::Distance),
+            _ => None
+        }
+    }
+
+    /// # `name`
+    /// Gets the name of the interior colouring mode, the inverse of `from_name`
+    pub fn name(&self) -> &'static str {
+        match self {
+            Interior::Flat => "flat",
+            Interior::Magnitude => "magnitude",
+            Interior::Angle => "angle",
+            Interior::Period => "period",
+            Interior::Distance => "distance"
+        }
+    }
+
+    /// # `next`
+    /// Gets the interior colouring mode that comes after this one, used to cycle through the modes
+    pub fn next(&self) -> Interior {
+        match self {
+            Interior::Flat => Interior::Magnitude,
+            Interior::Magnitude => Interior::Angle,
+            Interior::Angle => Interior::Period,
+            Interior::Period => Interior::Distance,
+            Interior::Distance => Interior::Flat
+        }
+    }
+
+    /// # `colour`
+    /// Gets the colour of a bounded pixel by taking its `Orbit` and the size of a pixel in the complex plane `f32`.
+    /// `None` if the exterior colouring should be used instead
+    pub fn colour(&self, orbit: &Orbit, pixel_size: f32) -> Option<u32> {
+        match self {
+            Interior::Flat => None,
+            Interior::Magnitude => Some(colours::gradient(colours::BLACK, colours::CERISE, orbit.z.norm() / 2.0)),
+            Interior::Angle => {
+                // Average angle is in the range [-pi, pi]
+                let angle = orbit.angle_sum / orbit.iterations.max(1) as f32;
+                Some(colours::gradient(colours::NAVY, colours::GOLD, (angle / std::f32::consts::PI + 1.0) / 2.0))
+            },
+            Interior::Period => Some(match orbit.period {
+                0 => colours::BLACK,
+                p => colours::LIGHT_THEME[(p - 1) % colours::LIGHT_THEME.len()]
+            }),
+            Interior::Distance => {
+                if !orbit.interior_distance.is_finite() {
+                    return Some(colours::BLACK);
+                }
+                let t = (orbit.interior_distance / pixel_size).max(1.0).ln() / INTERIOR_DISTANCE_RANGE;
+                Some(colours::gradient(colours::WHITE, colours::BLACK, t))
+            }
+        }
+    }
+}
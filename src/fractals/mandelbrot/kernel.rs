@@ -0,0 +1,201 @@
+use crate::math::{
+    vector::Vector2,
+    float::Float
+};
+
+use super::trap::OrbitTrap;
+
+// Consts
+const MAX_PERIOD : usize = 64; // Longest cycle looked for when colouring the interior
+const PERIOD_EPSILON : f64 = 1E-4; // How close the orbit has to come back to count as a cycle
+
+/// # `Orbit`
+/// What is left of the orbit of a point under f(z) = z^2 + C once the iteration stops, the colouring modes are based on this.
+/// The orbit is computed in `f64` but only needs `f32` for colouring
+#[derive(Copy, Clone, Debug)]
+pub struct Orbit {
+    pub iterations: usize,
+    pub escaped: bool,
+    pub z: Vector2, // Last value of z
+    pub dz: Vector2, // Derivative of z with respect to C, used for distance estimation
+    pub trap_distance: f32, // Closest the orbit came to the trap, infinite if there is no trap
+    pub trap_point: Vector2, // Point of the orbit closest to the trap, relative to the trap
+    pub angle_sum: f32, // Sum of the angles of every point of the orbit, only tracked for the interior
+    pub period: usize, // Length of the cycle a bounded orbit ends up in, 0 if unknown
+    pub interior_distance: f32 // Estimated distance from a bounded point to the boundary, infinite if unknown
+}
+
+/// # `Kernel`
+/// Settings of the escape time iteration
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Kernel {
+    pub max_iteration: usize,
+    pub bailout: f32, // Escape radius
+    pub trap: Option<OrbitTrap>, // Trap to measure the orbit against
+    pub interior: bool // Whether the data needed to colour the interior (angles, period, interior distance) should be computed
+}
+
+/// # `in_main_bulbs`
+/// Checks if c is inside the main cardioid or the period-2 bulb. These points never escape so they can skip the iterations
+pub fn in_main_bulbs<T: Float>(c: Vector2<T>) -> bool {
+    let (quarter, one) = (T::from_f64(0.25), T::one());
+    // Main cardioid: q(q + (x - 1/4)) <= y^2 / 4 where q = (x - 1/4)^2 + y^2
+    let q = (c.x - quarter) * (c.x - quarter) + c.y * c.y;
+    // Period-2 bulb: the circle of radius 1/4 around -1
+    q * (q + (c.x - quarter)) <= quarter * c.y * c.y || (c.x + one) * (c.x + one) + c.y * c.y <= quarter * quarter
+}
+
+impl Kernel {
+    /// # `formula`
+    /// Describes everything apart from the iteration limit that the resulting orbits depend on, used to tell cached orbits apart
+    pub fn formula(&self) -> String {
+        format!("z^2+c bailout {} trap {:?} interior {}", self.bailout, self.trap, self.interior)
+    }
+
+    /// # `iterate`
+    /// Iterates the point `c` until its orbit leaves the circle of radius `bailout` or `max_iteration` is reached.
+    /// If a `trap` is given or the interior is needed the whole orbit is needed, so the interior shortcuts are skipped
+    pub fn iterate(&self, c: Vector2<f64>) -> Orbit {
+        let bounded = Orbit {
+            iterations: self.max_iteration,
+            escaped: false,
+            z: Vector2::new(0.0, 0.0),
+            dz: Vector2::new(0.0, 0.0),
+            trap_distance: f32::INFINITY,
+            trap_point: Vector2::new(0.0, 0.0),
+            angle_sum: 0.0,
+            period: 0,
+            interior_distance: f32::INFINITY
+        };
+        let full_orbit = self.trap.is_some() || self.interior;
+
+        if !full_orbit && in_main_bulbs(c) {
+            return bounded;
+        }
+
+        // The Mandelbrot set is drawn using the Complex function f(z) = z^2 + C, fed recursively
+        // and for the different values of C tests if the value of the recursion blows up.
+        // A simple test is to see if after some iterations the complex modulous (norm, length or absolute value) is larger than 2
+        // Based on the number of iterations a different colour (or hue) is drawn on the screen in that corresponding pixel position
+
+        // Taking the "scalar" of the imaginary and real part
+        // (ignoring the complex hassle because we only need length and that can be computed without complex computations)
+        let mut x = 0.0;
+        let mut y = 0.0;
+        let mut dz = Vector2::<f64>::new(0.0, 0.0);
+        let mut iteration = 0; // amount of iterations
+        let bailout = (self.bailout * self.bailout) as f64;
+
+        // Periodicity detection (Brent's algorithm), a point is saved and compared to the following ones.
+        // If the orbit ever comes back to exactly the same point it is stuck in a cycle and will never escape.
+        // The saved point is replaced after 1, 2, 4, 8... iterations so cycles of any length are found
+        let mut saved = (x, y);
+        let mut cycle_length = 0;
+        let mut cycle_limit = 1;
+
+        let mut trap_distance = f32::INFINITY;
+        let mut trap_point = Vector2::new(0.0, 0.0);
+        let mut angle_sum = 0.0;
+
+        // Looping while the norm (x^2 + y^2) is less than the bailout (2^2 for the regular set) and while we are less than the upper limit of iterations
+        while x * x + y * y <= bailout && iteration < self.max_iteration {
+            // The derivative follows the chain rule, dz' = 2 * z * dz + 1
+            dz = 2.0 * Vector2::new(x, y).complex_mul(dz) + Vector2::new(1.0, 0.0);
+
+            // if we say z = (x+yi) then f(z) = x^2 - y^2 + 2xyi + c
+            // So x^2 - y^2 + c's real part is the new real part and
+            // 2xy + c's imaginary part is the new imaginary part's coefficient
+            let temp = x * x - y * y + c.x;
+            y = 2.0 * x * y + c.y;
+            x = temp;
+            iteration += 1;
+
+            if let Some(trap) = &self.trap {
+                let z = Vector2::new(x as f32, y as f32);
+                let distance = trap.distance(z);
+                if distance < trap_distance {
+                    trap_distance = distance;
+                    trap_point = trap.local(z);
+                }
+            }
+
+            if self.interior {
+                angle_sum += y.atan2(x) as f32;
+            }
+
+            if full_orbit {
+                continue;
+            }
+
+            if x == saved.0 && y == saved.1 {
+                return Orbit {z: Vector2::new(x as f32, y as f32), ..bounded};
+            }
+
+            cycle_length += 1;
+            if cycle_length == cycle_limit {
+                saved = (x, y);
+                cycle_length = 0;
+                cycle_limit *= 2;
+            }
+        }
+
+        let escaped = x * x + y * y > bailout;
+        let (period, interior_distance) = match self.interior && !escaped {
+            true => interior(Vector2::new(x, y), c),
+            false => (0, f32::INFINITY)
+        };
+
+        Orbit {
+            iterations: iteration,
+            escaped: escaped,
+            z: Vector2::new(x as f32, y as f32),
+            dz: dz.cast(),
+            trap_distance: trap_distance,
+            trap_point: trap_point,
+            angle_sum: angle_sum,
+            period: period,
+            interior_distance: interior_distance
+        }
+    }
+}
+
+/// # `interior`
+/// Takes the last point `z` of a bounded orbit and finds the length of the cycle it has settled in
+/// along with the estimated distance from `c` to the boundary of the set. Returns `(0, infinity)` if no cycle is found
+fn interior(z: Vector2<f64>, c: Vector2<f64>) -> (usize, f32) {
+    let square = |z: Vector2<f64>| z.complex_mul(z) + c;
+    let one = Vector2::new(1.0, 0.0);
+
+    // Period, the first time the orbit comes back close to where it was
+    let mut w = z;
+    let mut period = 0;
+    for p in 1..=MAX_PERIOD {
+        w = square(w);
+        if (w - z).norm() < PERIOD_EPSILON {
+            period = p;
+            break;
+        }
+    }
+    if period == 0 {
+        return (0, f32::INFINITY);
+    }
+
+    // Interior distance estimation, follows the first and second derivatives once around the cycle
+    // b = (1 - |dz|^2) / |dcdz + dzdz * dc / (1 - dz)|
+    let mut w = z;
+    let mut dz = one; // dw/dz
+    let mut dzdz = Vector2::new(0.0, 0.0); // d^2w/dz^2
+    let mut dc = Vector2::new(0.0, 0.0); // dw/dc
+    let mut dcdz = Vector2::new(0.0, 0.0); // d^2w/dcdz
+    for _ in 0..period {
+        dcdz = 2.0 * (w.complex_mul(dcdz) + dz.complex_mul(dc));
+        dc = 2.0 * w.complex_mul(dc) + one;
+        dzdz = 2.0 * (dz.complex_mul(dz) + w.complex_mul(dzdz));
+        dz = 2.0 * w.complex_mul(dz);
+        w = square(w);
+    }
+
+    let numerator = 1.0 - dz.norm() * dz.norm();
+    let denominator = (dcdz + dzdz.complex_mul(dc).complex_div(one - dz)).norm();
+    (period, (numerator / denominator) as f32)
+}
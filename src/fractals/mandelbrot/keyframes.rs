@@ -0,0 +1,136 @@
+use crate::math::vector::Vector2;
+
+use std::fs::File;
+use std::io::{Error, ErrorKind, Read};
+
+/// # `Easing`
+/// Curves that shape how the animation moves between two keyframes
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Easing {
+    Linear,
+    In, // Starts slow
+    Out, // Ends slow
+    InOut // Starts and ends slow
+}
+
+impl Easing {
+    /// # `from_name`
+    /// Gets the easing with the given name (`linear`, `in`, `out` or `in_out`), `None` if there is no such easing
+    pub fn from_name(name: &str) -> Option<Easing> {
+        match name.to_lowercase().trim() {
+            "linear" => Some(Easing::Linear),
+            "in" => Some(Easing::In),
+            "out" => Some(Easing::Out),
+            "in_out" => Some(Easing::InOut),
+            _ => None
+        }
+    }
+
+    /// # `apply`
+    /// Takes the progress `t` in the range [0, 1] and returns the eased progress, also in the range [0, 1]
+    pub fn apply(&self, t: f32) -> f32 {
+        match self {
+            Easing::Linear => t,
+            Easing::In => t * t,
+            Easing::Out => t * (2.0 - t),
+            Easing::InOut => t * t * (3.0 - 2.0 * t) // Smoothstep
+        }
+    }
+}
+
+/// # `Keyframe`
+/// A waypoint of a zoom animation
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Keyframe {
+    pub time: f32, // In seconds
+    pub centre: Vector2<f64>,
+    pub scale: f64, // Width of the view in the complex plane
+    pub rotation: f32, // In radians
+    pub palette_offset: f32, // Added to the iterations before colouring, cycles the colours
+    pub easing: Easing // Easing of the part of the animation leading up to this keyframe
+}
+
+impl Keyframe {
+    /// # `load`
+    /// Loads keyframes from a file. Every line is a keyframe written as
+    /// `time centre_x centre_y scale rotation palette_offset [easing]`, empty lines and lines starting with '#' are skipped.
+    /// Returns an error if the file can not be read, a line is invalid or the times are not increasing
+    pub fn load(path: &str) -> std::io::Result<Vec<Keyframe>> {
+        let mut text = String::new();
+        File::open(path)?.read_to_string(&mut text)?;
+
+        let mut keyframes : Vec<Keyframe> = Vec::new();
+        for (number, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let invalid = |message: &str| Error::new(ErrorKind::InvalidData, format!("\"{}\" line {}: {}", path, number + 1, message));
+            let fields : Vec<&str> = line.split_whitespace().collect();
+            if fields.len() < 6 {
+                return Err(invalid("expected time, centre x, centre y, scale, rotation and palette offset"));
+            }
+
+            let values = fields[0..6].iter()
+                .map(|field| field.parse::<f64>().map_err(|_| invalid("invalid number")))
+                .collect::<std::io::Result<Vec<f64>>>()?;
+            let easing = match fields.get(6) {
+                Some(name) => Easing::from_name(name).ok_or_else(|| invalid("unknown easing"))?,
+                None => Easing::Linear
+            };
+
+            if values[3] <= 0.0 {
+                return Err(invalid("the scale must be positive"));
+            }
+            if keyframes.last().is_some_and(|last| last.time >= values[0] as f32) {
+                return Err(invalid("keyframe times must be increasing"));
+            }
+
+            keyframes.push(Keyframe {
+                time: values[0] as f32,
+                centre: Vector2::new(values[1], values[2]),
+                scale: values[3],
+                rotation: values[4] as f32,
+                palette_offset: values[5] as f32,
+                easing: easing
+            });
+        }
+
+        if keyframes.is_empty() {
+            return Err(Error::new(ErrorKind::InvalidData, format!("\"{}\" has no keyframes", path)));
+        }
+        Ok(keyframes)
+    }
+
+    /// # `at`
+    /// Interpolates the keyframes (sorted by time) at the given `time`. The scale is interpolated exponentially
+    /// so a zoom has a constant speed, and the centre moves along with the scale so the target point stays put on screen
+    pub fn at(keyframes: &[Keyframe], time: f32) -> Keyframe {
+        let next = match keyframes.iter().position(|keyframe| keyframe.time > time) {
+            Some(0) => return keyframes[0],
+            Some(idx) => idx,
+            None => return *keyframes.last().unwrap()
+        };
+        let from = keyframes[next - 1];
+        let to = keyframes[next];
+
+        let t = to.easing.apply((time - from.time) / (to.time - from.time));
+        let scale = from.scale * (to.scale / from.scale).powf(t as f64);
+
+        // How far the centre has moved, follows the zoom when there is one
+        let weight = match (from.scale - to.scale).abs() > f64::EPSILON * from.scale {
+            true => (from.scale - scale) / (from.scale - to.scale),
+            false => t as f64
+        };
+
+        Keyframe {
+            time: time,
+            centre: from.centre + (to.centre - from.centre) * weight,
+            scale: scale,
+            rotation: from.rotation + (to.rotation - from.rotation) * t,
+            palette_offset: from.palette_offset + (to.palette_offset - from.palette_offset) * t,
+            easing: to.easing
+        }
+    }
+}
@@ -0,0 +1,711 @@
+use crate::visuals::{
+    visualizer::{Visualizer, U_WIDTH, U_HEIGHT, HEIGHT, WIDTH},
+    image::Image,
+    colours};
+use crate::math::{
+    vector::Vector2,
+    viewport::Viewport,
+    random::Random
+};
+use crate::fractals::bookmarks::{Bookmark, BOOKMARK_FILE};
+use crate::cli;
+use minifb::{Key, KeyRepeat, MouseButton, MouseMode};
+
+use kernel::{Kernel, Orbit};
+use colouring::{Colouring, Interior};
+use trap::OrbitTrap;
+use sampling::Sampling;
+use keyframes::Keyframe;
+use tiles::TileCache;
+
+use std::time::{Duration, Instant};
+
+// Consts
+pub const MAX_ITERATION : usize = 300; // Iteration limit at the starting scale
+const ITERATIONS_PER_ZOOM : f32 = 60.0; // Iterations added to the automatic limit every time the scale halves
+const ITERATION_STEP : f32 = 1.25; // Iteration limit multiplier of a +/- key press
+const MIN_ITERATION : usize = 16;
+const ZOOM_FACTOR : f64 = 0.5; // Scale multiplier of a click, right click uses the inverse
+const SCROLL_ZOOM_FACTOR : f64 = 0.9; // Scale multiplier per scroll wheel step
+const PAN_STEP : f32 = 0.1; // Fraction of the view moved per arrow key press
+const DRAG_THRESHOLD : f32 = 3.0; // Pixels the mouse has to move before a click becomes a drag
+const ROTATION_STEP : f64 = std::f64::consts::PI / 36.0; // Radians rotated per Q/E key press
+const HOME_SCALE : f64 = 4.0; // Width of the view in the complex plane when reset
+const BLOCK_SIZES : [usize; 5] = [16, 8, 4, 2, 1]; // Refinement passes, from a coarse block preview down to single pixels
+const ADAPTIVE_THRESHOLD : u32 = 16; // Channel difference to a neighbour above which a pixel is supersampled in adaptive mode
+const FRAME_BUDGET : Duration = Duration::from_millis(50); // Time spent refining before the window is updated and input is checked
+const DEFAULT_FPS : f32 = 30.0;
+const DEFAULT_SAMPLES : usize = 2; // Samples per side of a pixel when --jitter or --adaptive is given without --samples
+const DEFAULT_FRAMES_DIR : &str = "frames";
+const TILE_CACHE_CAPACITY : usize = 256; // Tiles kept in the tile cache, a bit under two screens worth of pixels at every zoom level visited
+const HOME_POINT : Vector2<f64> = Vector2 {x: -0.5, y: 0.0};
+const ZOOM_POINT : Vector2<f64> = Vector2{x: -1.139083E-1, y: 8.990149E-1};
+
+/// # `Settings`
+/// Options of the Mandelbrot visualizer that can be given from the command line
+pub struct Settings {
+    pub colouring: Colouring,
+    pub interior: Interior,
+    pub trap: OrbitTrap,
+    pub trap_image: Option<Image>,
+    pub sampling: Sampling,
+    pub keyframes: Option<Vec<Keyframe>>,
+    pub fps: f32,
+    pub frames_dir: String,
+    pub bookmark: Option<Bookmark>,
+    pub tile_file: Option<String>,
+    pub iterations: Option<usize>
+}
+
+impl Settings {
+    /// # `from_args`
+    /// Reads the settings from the command line arguments, `--colouring <bands|distance|slope|trap>`,
+    /// `--interior <flat|magnitude|angle|period|distance>`, `--trap <point|cross|circle|line>` and `--trap-image <file.ppm>`.
+    /// Giving a trap picks the trap colouring unless another colouring is given.
+    /// Anti-aliasing is set with `--samples <n>` (n x n samples per pixel), `--jitter` and `--adaptive`.
+    /// `--keyframes <file>` renders a zoom animation at `--fps <fps>` into the `--frames <directory>` instead of the interactive view.
+    /// `--bookmark <name>` starts at a saved bookmark, with its colouring modes unless others are given.
+    /// `--tile-cache <file>` loads the computed tiles from the file and saves them to it when the window closes.
+    /// `--iterations <n>` fixes the iteration limit instead of raising it automatically while zooming in
+    pub fn from_args(args: &[String]) -> Settings {
+        let bookmark = cli::flag_value(args, "--bookmark")
+            .map(|name| Bookmark::find(BOOKMARK_FILE, name).unwrap_or_else(|err| panic!("Could not load the bookmark: {}", err)));
+        if let Some(bookmark) = &bookmark {
+            if bookmark.fractal != "mandelbrot" {
+                panic!("Bookmark \"{}\" is of the {} fractal", bookmark.name, bookmark.fractal)
+            }
+        }
+
+        let trap = cli::flag_value(args, "--trap")
+            .map(|name| OrbitTrap::from_name(name).unwrap_or_else(|| panic!("No such orbit trap \"{}\"", name)));
+
+        let colouring = match cli::flag_value(args, "--colouring") {
+            Some(name) => Colouring::from_name(name).unwrap_or_else(|| panic!("No such colouring \"{}\"", name)),
+            None if trap.is_some() => Colouring::Trap,
+            None => match &bookmark {
+                Some(bookmark) => Colouring::from_name(&bookmark.colouring)
+                    .unwrap_or_else(|| panic!("No such colouring \"{}\" in bookmark \"{}\"", bookmark.colouring, bookmark.name)),
+                None => Colouring::Bands
+            }
+        };
+
+        let interior = match (cli::flag_value(args, "--interior"), &bookmark) {
+            (Some(name), _) => Interior::from_name(name).unwrap_or_else(|| panic!("No such interior colouring \"{}\"", name)),
+            (None, Some(bookmark)) => Interior::from_name(&bookmark.interior)
+                .unwrap_or_else(|| panic!("No such interior colouring \"{}\" in bookmark \"{}\"", bookmark.interior, bookmark.name)),
+            (None, None) => Interior::Flat
+        };
+
+        let trap_image = cli::flag_value(args, "--trap-image")
+            .map(|path| Image::load_ppm(path).unwrap_or_else(|err| panic!("Could not load the trap image: {}", err)));
+
+        // --jitter and --adaptive need more than one sample per pixel, they turn anti-aliasing on when --samples is not given
+        let (jitter, adaptive) = (cli::has_flag(args, "--jitter"), cli::has_flag(args, "--adaptive"));
+        let grid = cli::flag_value(args, "--samples")
+            .map(|n| n.parse::<usize>().unwrap_or_else(|_| panic!("Invalid amount of samples \"{}\"", n)))
+            .unwrap_or(if jitter || adaptive { DEFAULT_SAMPLES } else { 1 });
+        if (jitter || adaptive) && grid < 2 {
+            panic!("--jitter and --adaptive need at least 2 samples, got --samples {}", grid);
+        }
+
+        let keyframes = cli::flag_value(args, "--keyframes")
+            .map(|path| Keyframe::load(path).unwrap_or_else(|err| panic!("Could not load the keyframes: {}", err)));
+
+        let fps = cli::flag_value(args, "--fps")
+            .map(|fps| fps.parse::<f32>().ok().filter(|fps| *fps > 0.0).unwrap_or_else(|| panic!("Invalid fps \"{}\"", fps)))
+            .unwrap_or(DEFAULT_FPS);
+
+        Settings {
+            colouring: colouring,
+            interior: interior,
+            trap: trap.unwrap_or(OrbitTrap::Point(Vector2::new(0.0, 0.0))),
+            trap_image: trap_image,
+            sampling: Sampling {
+                grid: grid.max(1),
+                jitter: jitter,
+                adaptive: adaptive
+            },
+            keyframes: keyframes,
+            fps: fps,
+            frames_dir: cli::flag_value(args, "--frames").unwrap_or(DEFAULT_FRAMES_DIR).to_string(),
+            bookmark: bookmark,
+            tile_file: cli::flag_value(args, "--tile-cache").map(|path| path.to_string()),
+            iterations: cli::flag_value(args, "--iterations")
+                .map(|n| n.parse::<usize>().ok().filter(|n| *n > 0).unwrap_or_else(|| panic!("Invalid iteration limit \"{}\"", n)))
+        }
+    }
+}
+
+/// # `adaptive_iterations`
+/// Gets the automatic iteration limit for a view of the given width in the complex plane.
+/// Deeper zooms need more iterations to tell the points near the boundary apart, so the limit grows with log(1 / scale)
+pub fn adaptive_iterations(scale: f64) -> usize {
+    MAX_ITERATION + (ITERATIONS_PER_ZOOM * (HOME_SCALE / scale).log2().max(0.0) as f32) as usize
+}
+
+/// # `Drag`
+/// State of the left mouse button while it is held down
+struct Drag {
+    start: Vector2, // Mouse position where the button was pressed
+    shifted: (isize, isize), // Pixels the view has been panned by since the button was pressed
+    moved: bool // Whether the mouse moved far enough for this to be a drag rather than a click
+}
+
+/// # `Mandelbrot`
+/// Structures that visualizes (with a lot of lags) the Mandelbrot set.
+/// Click to zoom in, right click to zoom out, drag to pan, scroll to zoom around the cursor,
+/// arrow keys to pan, Q/E to rotate, R to reset the view, P to jump to a nice point, C to cycle the colouring modes, I to cycle the interior colouring modes
+/// B to bookmark the current view and +/- to raise or lower the iteration limit.
+/// The view is rendered progressively, first as coarse blocks then refined down to single pixels and finally anti-aliased if enabled.
+/// The view and the orbits are computed in `f64`, which allows zooming in about 10^12 times before neighbouring pixels can no longer be told apart
+pub struct Mandelbrot {
+    visualizer: Visualizer,
+    viewport: Viewport<f64>,
+    max_iteration: usize, // Iteration limit of the current view
+    iteration_override: Option<usize>, // Manual iteration limit, the limit follows the scale when None
+    iteration_factor: f32, // Multiplier of the automatic iteration limit, changed with the +/- keys
+    palette_offset: f32,
+    drag: Option<Drag>,
+    right_down: bool,
+    colouring: Colouring,
+    interior: Interior,
+    trap: OrbitTrap,
+    trap_image: Option<Image>,
+    sampling: Sampling,
+    random: Random, // Used for jittered sampling, fixed seed so that renders are repeatable
+    orbits: Vec<Option<Orbit>>, // Orbits of every pixel of the current view, None if not yet computed
+    orbits_view: Viewport<f64>, // View and kernel the orbits were computed with
+    orbits_kernel: Kernel,
+    tiles: TileCache, // Orbits of previously visited views
+    tile_file: Option<String>, // File the tile cache is loaded from and saved to
+    pass: usize, // Index of the current refinement pass in BLOCK_SIZES, the anti-aliasing pass comes after them
+    row: usize, // Next row of blocks to compute in the current pass
+    render_start: Instant, // When the current view started rendering
+    render_time: Option<Duration>, // How long the current view took to fully render, None while still rendering
+    keyframes: Option<Vec<Keyframe>>, // Zoom animation to render instead of the interactive view
+    animating: bool, // Whether keyframes are being rendered, their views are not snapped to the tile grid
+    fps: f32,
+    frames_dir: String
+}
+
+impl Mandelbrot {
+    /// # `new`
+    /// Initializes the Mandelbrot visualizer with the given `Settings`
+    pub fn new(settings: Settings) -> Mandelbrot {
+        let mut viewport = Viewport::new(HOME_POINT, HOME_SCALE, Vector2::new(WIDTH, HEIGHT).cast());
+        let mut iteration_override = settings.iterations;
+        let mut palette_offset = 0.0;
+        if let Some(bookmark) = &settings.bookmark {
+            viewport.centre = bookmark.centre;
+            viewport.scale = bookmark.scale;
+            viewport.rotation = bookmark.rotation as f64;
+            iteration_override = iteration_override.or(Some(bookmark.max_iteration));
+            palette_offset = bookmark.palette_offset;
+        }
+
+        let base_pixel_size = HOME_SCALE / WIDTH as f64;
+        let tiles = match &settings.tile_file {
+            Some(path) => match TileCache::load(path, base_pixel_size, TILE_CACHE_CAPACITY) {
+                Ok(tiles) => {
+                    println!("Loaded the tile cache from \"{}\"", path);
+                    tiles
+                },
+                Err(err) => {
+                    println!("Starting a new tile cache ({})", err);
+                    TileCache::new(base_pixel_size, TILE_CACHE_CAPACITY)
+                }
+            },
+            None => TileCache::new(base_pixel_size, TILE_CACHE_CAPACITY)
+        };
+
+        let mut mandelbrot = Mandelbrot {
+            visualizer: Visualizer::new(None),
+            viewport: viewport,
+            max_iteration: MAX_ITERATION, // Set by `invalidate` below
+            iteration_override: iteration_override,
+            iteration_factor: 1.0,
+            palette_offset: palette_offset,
+            drag: None,
+            right_down: false,
+            colouring: settings.colouring,
+            interior: settings.interior,
+            trap: settings.trap,
+            trap_image: settings.trap_image,
+            sampling: settings.sampling,
+            random: Random::new(0),
+            orbits: vec![None; U_WIDTH * U_HEIGHT],
+            orbits_view: viewport, // Both set by `invalidate` below
+            orbits_kernel: Kernel {max_iteration: MAX_ITERATION, bailout: settings.colouring.bailout(), trap: None, interior: false},
+            tiles: tiles,
+            tile_file: settings.tile_file,
+            pass: 0,
+            row: 0,
+            render_start: Instant::now(),
+            render_time: None,
+            keyframes: settings.keyframes,
+            animating: false,
+            fps: settings.fps,
+            frames_dir: settings.frames_dir
+        };
+
+        mandelbrot.invalidate();
+        mandelbrot
+    }
+
+    /// # `invalidate`
+    /// Forgets every computed pixel and restarts the refinement, used when the scale of the view changes.
+    /// The computed pixels are kept in the tile cache and the new view starts from the pixels cached for it.
+    /// Keyframe views are not snapped to the tile grid since that would make the animation jitter
+    fn invalidate(&mut self) {
+        self.cache_orbits();
+        if !self.animating {
+            self.tiles.snap(&mut self.viewport);
+        }
+        self.max_iteration = match self.iteration_override {
+            Some(limit) => limit,
+            None => ((adaptive_iterations(self.viewport.scale) as f32 * self.iteration_factor).round() as usize).max(MIN_ITERATION)
+        };
+        self.orbits = vec![None; U_WIDTH * U_HEIGHT];
+        self.fetch_orbits();
+        self.restart();
+    }
+
+    /// # `cache_orbits`
+    /// Stores the computed pixels in the tile cache, under the view and kernel they were computed with
+    fn cache_orbits(&mut self) {
+        if let Some(grid) = self.tiles.grid(&self.orbits_view) {
+            self.tiles.store(&self.orbits_kernel, grid, &self.orbits, U_WIDTH, U_HEIGHT);
+        }
+    }
+
+    /// # `fetch_orbits`
+    /// Fills the pixels that are not computed yet with the pixels cached for the current view and kernel
+    fn fetch_orbits(&mut self) {
+        self.orbits_view = self.viewport;
+        self.orbits_kernel = self.kernel();
+        if let Some(grid) = self.tiles.grid(&self.viewport) {
+            self.tiles.fetch(&self.orbits_kernel, grid, &mut self.orbits, U_WIDTH, U_HEIGHT);
+        }
+    }
+
+    /// # `restart`
+    /// Restarts the refinement passes and the render timer
+    fn restart(&mut self) {
+        self.pass = 0;
+        self.row = 0;
+        self.render_start = Instant::now();
+        self.render_time = None;
+    }
+
+    /// # `pan_pixels`
+    /// Moves the view by a whole amount of pixels. Since every pixel keeps its complex coordinate
+    /// the already computed pixels are shifted and reused, only the uncovered area has to be computed
+    fn pan_pixels(&mut self, dx: isize, dy: isize) {
+        if dx == 0 && dy == 0 {
+            return;
+        }
+
+        self.cache_orbits();
+        self.viewport.pan(Vector2::from_isize((dx, dy)));
+
+        let mut shifted = vec![None; U_WIDTH * U_HEIGHT];
+        for py in 0..U_HEIGHT as isize {
+            for px in 0..U_WIDTH as isize {
+                let (nx, ny) = (px - dx, py - dy);
+                if nx >= 0 && ny >= 0 && nx < U_WIDTH as isize && ny < U_HEIGHT as isize {
+                    shifted[nx as usize + ny as usize * U_WIDTH] = self.orbits[px as usize + py as usize * U_WIDTH];
+                }
+            }
+        }
+
+        self.orbits = shifted;
+        self.fetch_orbits();
+        self.restart();
+    }
+
+    /// # `handle_input`
+    /// Applies the mouse and keyboard gestures to the view. Returns true if the view changed
+    fn handle_input(&mut self) -> bool {
+        let window = &self.visualizer.window;
+        let mouse = window.get_mouse_pos(MouseMode::Clamp).map(|pos| Vector2::new(pos.0, pos.1));
+        let left_down = window.get_mouse_down(MouseButton::Left);
+        let right_down = window.get_mouse_down(MouseButton::Right);
+        let scroll = window.get_scroll_wheel();
+        let mut changed = false;
+
+        if let Some(mouse) = mouse {
+            // Left button, either a drag (pan) or a click (zoom in) on release
+            match (&mut self.drag, left_down) {
+                (None, true) => self.drag = Some(Drag {start: mouse, shifted: (0, 0), moved: false}),
+                (Some(drag), true) => {
+                    let delta = mouse - drag.start;
+                    if drag.moved || delta.norm() > DRAG_THRESHOLD {
+                        // Panned in whole pixels so that computed pixels can be reused
+                        let total = (-delta.x.round() as isize, -delta.y.round() as isize);
+                        let step = (total.0 - drag.shifted.0, total.1 - drag.shifted.1);
+                        drag.moved = true;
+                        drag.shifted = total;
+                        self.pan_pixels(step.0, step.1);
+                        changed = true;
+                    }
+                },
+                (Some(drag), false) => {
+                    if !drag.moved {
+                        self.viewport.centre = self.viewport.pixel_to_world(mouse.cast());
+                        self.viewport.scale *= ZOOM_FACTOR;
+                        self.invalidate();
+                        changed = true;
+                    }
+                    self.drag = None;
+                },
+                (None, false) => {}
+            }
+
+            // Right click (on release) zooms out
+            if self.right_down && !right_down {
+                self.viewport.centre = self.viewport.pixel_to_world(mouse.cast());
+                self.viewport.scale /= ZOOM_FACTOR;
+                self.invalidate();
+                changed = true;
+            }
+
+            // Scrolling zooms around the cursor
+            if let Some((_, steps)) = scroll {
+                if steps != 0.0 {
+                    self.viewport.zoom_at(mouse.cast(), SCROLL_ZOOM_FACTOR.powf(steps.signum() as f64));
+                    self.invalidate();
+                    changed = true;
+                }
+            }
+        }
+        self.right_down = right_down;
+
+        // Keyboard panning and resets
+        let window = &self.visualizer.window;
+        let pan = Vector2::new(
+            match (window.is_key_pressed(Key::Left, KeyRepeat::Yes), window.is_key_pressed(Key::Right, KeyRepeat::Yes)) {
+                (true, false) => -PAN_STEP,
+                (false, true) => PAN_STEP,
+                _ => 0.0
+            },
+            match (window.is_key_pressed(Key::Up, KeyRepeat::Yes), window.is_key_pressed(Key::Down, KeyRepeat::Yes)) {
+                (true, false) => -PAN_STEP,
+                (false, true) => PAN_STEP,
+                _ => 0.0
+            });
+        let reset = window.is_key_pressed(Key::R, KeyRepeat::No);
+        let jump = window.is_key_pressed(Key::P, KeyRepeat::No);
+        let cycle_colouring = window.is_key_pressed(Key::C, KeyRepeat::No);
+        let cycle_interior = window.is_key_pressed(Key::I, KeyRepeat::No);
+        let bookmark = window.is_key_pressed(Key::B, KeyRepeat::No);
+        let iteration_step = match (window.is_key_pressed(Key::Equal, KeyRepeat::Yes) || window.is_key_pressed(Key::NumPadPlus, KeyRepeat::Yes),
+                                    window.is_key_pressed(Key::Minus, KeyRepeat::Yes) || window.is_key_pressed(Key::NumPadMinus, KeyRepeat::Yes)) {
+            (true, false) => ITERATION_STEP,
+            (false, true) => 1.0 / ITERATION_STEP,
+            _ => 1.0
+        };
+        let rotate = match (window.is_key_pressed(Key::Q, KeyRepeat::Yes), window.is_key_pressed(Key::E, KeyRepeat::Yes)) {
+            (true, false) => -ROTATION_STEP,
+            (false, true) => ROTATION_STEP,
+            _ => 0.0
+        };
+
+        if pan != Vector2::new(0.0, 0.0) {
+            self.pan_pixels((pan.x * WIDTH) as isize, (pan.y * HEIGHT) as isize);
+            changed = true;
+        }
+
+        if rotate != 0.0 {
+            self.viewport.rotation += rotate;
+            self.invalidate();
+            changed = true;
+        }
+
+        if reset {
+            self.viewport = Viewport::new(HOME_POINT, HOME_SCALE, self.viewport.screen);
+            self.invalidate();
+            changed = true;
+        }
+
+        if jump {
+            self.viewport.centre = ZOOM_POINT;
+            self.invalidate();
+            changed = true;
+        }
+
+        // The modes can use different escape radii, so the orbits have to be recomputed
+        if cycle_colouring {
+            self.colouring = self.colouring.next();
+            self.invalidate();
+            changed = true;
+        }
+
+        if cycle_interior {
+            self.interior = self.interior.next();
+            self.invalidate();
+            changed = true;
+        }
+
+        // A manual limit is changed directly, otherwise the automatic limit is scaled so it keeps following the zoom
+        if iteration_step != 1.0 {
+            match &mut self.iteration_override {
+                Some(limit) => *limit = ((*limit as f32 * iteration_step).round() as usize).max(MIN_ITERATION),
+                None => self.iteration_factor *= iteration_step
+            }
+            self.invalidate();
+            changed = true;
+        }
+
+        if bookmark {
+            self.save_bookmark();
+        }
+
+        changed
+    }
+
+    /// # `save_bookmark`
+    /// Saves the current view and colouring to the bookmark file under a new name, which is printed
+    fn save_bookmark(&self) {
+        let saved = Bookmark::unused_name(BOOKMARK_FILE, "mandelbrot")
+            .and_then(|name| {
+                Bookmark::add(BOOKMARK_FILE, Bookmark {
+                    name: name.clone(),
+                    fractal: "mandelbrot".to_string(),
+                    centre: self.viewport.centre,
+                    scale: self.viewport.scale,
+                    rotation: self.viewport.rotation as f32,
+                    max_iteration: self.max_iteration,
+                    colouring: self.colouring.name().to_string(),
+                    interior: self.interior.name().to_string(),
+                    palette_offset: self.palette_offset
+                })?;
+                Ok(name)
+            });
+
+        match saved {
+            Ok(name) => println!("Saved bookmark \"{}\", open it again with `--bookmark {}`", name, name),
+            Err(err) => println!("Could not save the bookmark: {}", err)
+        }
+    }
+
+    /// # `update_title`
+    /// Shows the current centre, scale and iteration limit in the window title, along with the render time once the view is fully rendered
+    fn update_title(&mut self) {
+        let viewport = self.viewport;
+        let mut title = format!("Mandelbrot - centre ({:.7}, {:.7}) scale {:.3e} rotation {:.0}° - {} iterations ({}) - {:?} / {:?} interior",
+                                viewport.centre.x, viewport.centre.y, viewport.scale, viewport.rotation.to_degrees(), self.max_iteration,
+                                if self.iteration_override.is_some() { "manual" } else { "auto" }, self.colouring, self.interior);
+        if let Some(time) = self.render_time {
+            title.push_str(&format!(" - rendered in {} ms", time.as_millis()));
+        }
+        self.visualizer.window.set_title(&title);
+    }
+
+    /// # `kernel`
+    /// Gets the escape time settings needed by the current colouring modes
+    fn kernel(&self) -> Kernel {
+        Kernel {
+            max_iteration: self.max_iteration,
+            bailout: self.colouring.bailout(),
+            trap: if self.colouring == Colouring::Trap { Some(self.trap) } else { None },
+            interior: self.interior != Interior::Flat
+        }
+    }
+
+    /// # `pixel_colour`
+    /// Gets the colour of an `Orbit` with the current exterior and interior colouring modes
+    fn pixel_colour(&self, orbit: &Orbit, pixel_size: f32) -> u32 {
+        match orbit.escaped {
+            true => None,
+            false => self.interior.colour(orbit, pixel_size)
+        }.unwrap_or_else(|| self.colouring.colour(orbit, pixel_size, self.palette_offset, self.trap_image.as_ref()))
+    }
+
+    /// # `pass_count`
+    /// Amount of passes needed to fully render a view, the block passes followed by the anti-aliasing pass if enabled
+    fn pass_count(&self) -> usize {
+        BLOCK_SIZES.len() + if self.sampling.enabled() { 1 } else { 0 }
+    }
+
+    /// # `refine`
+    /// Continues the progressive rendering until the `deadline` passes or the view is fully computed.
+    /// Each pass computes one sample per block and paints the whole block with it, pixels that are already known keep their own colour
+    fn refine(&mut self, deadline: Instant) {
+        while self.pass < self.pass_count() && Instant::now() < deadline {
+            if self.pass == BLOCK_SIZES.len() {
+                self.antialias_row(self.row);
+                self.row += 1;
+            }
+            else {
+                let size = BLOCK_SIZES[self.pass];
+                self.block_row(self.row, size);
+                self.row += size;
+            }
+
+            // Next row of blocks, or the next (finer) pass
+            if self.row >= U_HEIGHT {
+                self.pass += 1;
+                self.row = 0;
+
+                if self.pass == self.pass_count() {
+                    self.render_time = Some(self.render_start.elapsed());
+                    self.update_title();
+                }
+            }
+        }
+    }
+
+    /// # `block_row`
+    /// Computes one sample for every block of the given `size` in the row of blocks starting at pixel row `by`
+    fn block_row(&mut self, by: usize, size: usize) {
+        let pixel_size = self.viewport.pixel_size() as f32;
+        let kernel = self.kernel();
+
+        for bx in (0..U_WIDTH).step_by(size) {
+            let sample = match self.orbits[bx + by * U_WIDTH] {
+                Some(orbit) => orbit,
+                None => {
+                    let c = self.viewport.pixel_to_world(Vector2::from_isize((bx as isize, by as isize)));
+                    let orbit = kernel.iterate(c);
+                    self.orbits[bx + by * U_WIDTH] = Some(orbit);
+                    orbit
+                }
+            };
+
+            for py in by..(by + size).min(U_HEIGHT) {
+                for px in bx..(bx + size).min(U_WIDTH) {
+                    let orbit = self.orbits[px + py * U_WIDTH].unwrap_or(sample);
+                    let colour = self.pixel_colour(&orbit, pixel_size);
+                    let _ = self.visualizer.set_pixel(Vector2::from_isize((px as isize, py as isize)), colour);
+                }
+            }
+        }
+    }
+
+    /// # `antialias_row`
+    /// Supersamples every pixel of the pixel row `py` and averages the colours of the samples.
+    /// In adaptive mode only pixels whose colour differs from one of their neighbours are supersampled
+    fn antialias_row(&mut self, py: usize) {
+        let pixel_size = self.viewport.pixel_size() as f32;
+        let kernel = self.kernel();
+        let base = |mandelbrot: &Mandelbrot, px: usize, py: usize| match mandelbrot.orbits[px + py * U_WIDTH] {
+            Some(orbit) => mandelbrot.pixel_colour(&orbit, pixel_size),
+            None => colours::BLACK
+        };
+
+        for px in 0..U_WIDTH {
+            if self.sampling.adaptive {
+                let colour = base(self, px, py);
+                let neighbours = [(px.wrapping_sub(1), py), (px + 1, py), (px, py.wrapping_sub(1)), (px, py + 1)];
+                let differs = neighbours.iter()
+                    .filter(|(nx, ny)| *nx < U_WIDTH && *ny < U_HEIGHT)
+                    .any(|(nx, ny)| colours::difference(colour, base(self, *nx, *ny)) > ADAPTIVE_THRESHOLD);
+
+                if !differs {
+                    continue;
+                }
+            }
+
+            let samples : Vec<u32> = self.sampling.offsets(&mut self.random).iter()
+                .map(|offset| {
+                    let c = self.viewport.pixel_to_world(Vector2::new(px as f64 + offset.x as f64, py as f64 + offset.y as f64));
+                    self.pixel_colour(&kernel.iterate(c), pixel_size)
+                })
+                .collect();
+            let _ = self.visualizer.set_pixel(Vector2::from_isize((px as isize, py as isize)), colours::average(&samples));
+        }
+    }
+
+    /// # `animate`
+    /// Renders the zoom animation through the given keyframes into numbered PPM frames.
+    /// Every frame is fully rendered from the interpolated keyframe so the output is the same on every run
+    fn animate(&mut self, keyframes: Vec<Keyframe>) {
+        if let Err(err) = std::fs::create_dir_all(&self.frames_dir) {
+            panic!("Could not create the frames directory \"{}\": {}", self.frames_dir, err)
+        }
+
+        self.animating = true;
+        let duration = keyframes.last().unwrap().time;
+        let frame_count = (duration * self.fps).ceil() as usize + 1;
+
+        for frame in 0..frame_count {
+            if !self.visualizer.window.is_open() {
+                println!("Animation stopped at frame {} of {}", frame, frame_count);
+                return;
+            }
+
+            let keyframe = Keyframe::at(&keyframes, frame as f32 / self.fps);
+            self.viewport.centre = keyframe.centre;
+            self.viewport.scale = keyframe.scale;
+            self.viewport.rotation = keyframe.rotation as f64;
+            self.palette_offset = keyframe.palette_offset;
+            self.random = Random::new(frame as u64);
+            self.invalidate();
+
+            while self.pass < self.pass_count() {
+                self.refine(Instant::now() + FRAME_BUDGET);
+                self.visualizer.apply_buffer();
+            }
+
+            let image = Image {width: U_WIDTH, height: U_HEIGHT, pixels: self.visualizer.buffer.clone()};
+            let path = format!("{}/frame_{:05}.ppm", self.frames_dir, frame);
+            if let Err(err) = image.save_ppm(&path) {
+                panic!("Could not save \"{}\": {}", path, err)
+            }
+            self.visualizer.window.set_title(&format!("Mandelbrot - rendered frame {} of {}", frame + 1, frame_count));
+        }
+
+        println!("Rendered {} frames into \"{}\"", frame_count, self.frames_dir);
+        self.visualizer.end();
+    }
+
+    /// # `save_tiles`
+    /// Saves the tile cache to its file, if one was given
+    fn save_tiles(&mut self) {
+        if let Some(path) = self.tile_file.clone() {
+            self.cache_orbits();
+            match self.tiles.save(&path) {
+                Ok(()) => println!("Saved the tile cache to \"{}\"", path),
+                Err(err) => println!("Could not save the tile cache: {}", err)
+            }
+        }
+    }
+
+    /// # `draw`
+    /// Draws the fractal on the screen, refining the current view a bit every frame. Renders the zoom animation instead if keyframes were given
+    pub fn draw(&mut self) {
+        if let Some(keyframes) = self.keyframes.take() {
+            self.animate(keyframes);
+            self.save_tiles();
+            return;
+        }
+
+        self.update_title();
+
+        while self.visualizer.window.is_open() {
+            if self.pass < self.pass_count() {
+                self.refine(Instant::now() + FRAME_BUDGET);
+                self.visualizer.apply_buffer();
+            }
+            else {
+                self.visualizer.window.update();
+            }
+
+            if self.handle_input() {
+                self.update_title();
+            }
+        }
+
+        self.save_tiles();
+    }
+
+}
+
+// === SUB MODS ===
+
+pub mod kernel;
+pub mod colouring;
+pub mod trap;
+pub mod sampling;
+pub mod keyframes;
+pub mod tiles;
@@ -0,0 +1,41 @@
+use crate::math::{
+    vector::Vector2,
+    random::Random
+};
+
+/// # `Sampling`
+/// How many samples are taken per pixel when anti-aliasing, and where
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Sampling {
+    pub grid: usize, // Samples are taken on a grid x grid pattern inside the pixel, 1 turns anti-aliasing off
+    pub jitter: bool, // Whether every sample is moved randomly within its grid cell (stochastic sampling)
+    pub adaptive: bool // Whether only pixels that differ from their neighbours are supersampled
+}
+
+impl Sampling {
+    /// # `enabled`
+    /// Checks if more than one sample is taken per pixel
+    pub fn enabled(&self) -> bool {
+        self.grid > 1
+    }
+
+    /// # `offsets`
+    /// Returns the positions of the samples within a pixel, each in the range [0, 1).
+    /// Jittered positions are drawn from the given `Random`
+    pub fn offsets(&self, random: &mut Random) -> Vec<Vector2> {
+        let cell = 1.0 / self.grid as f32;
+        let mut offsets = Vec::with_capacity(self.grid * self.grid);
+
+        for sy in 0..self.grid {
+            for sx in 0..self.grid {
+                let (jx, jy) = match self.jitter {
+                    true => (random.next_f32(), random.next_f32()),
+                    false => (0.5, 0.5) // Centre of the cell
+                };
+                offsets.push(Vector2::new((sx as f32 + jx) * cell, (sy as f32 + jy) * cell));
+            }
+        }
+
+        offsets
+    }
+}
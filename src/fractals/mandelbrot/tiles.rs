@@ -0,0 +1,307 @@
+use crate::math::{
+    vector::Vector2,
+    viewport::Viewport
+};
+
+use super::kernel::{Kernel, Orbit};
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{Error, ErrorKind, Read, Write};
+
+// Consts
+pub const TILE_SIZE : usize = 64; // Width and height of a tile in pixels
+const LEVEL_TOLERANCE : f64 = 1E-4; // How far (in powers of two) the scale may be from a zoom level
+const GRID_TOLERANCE : f64 = 1E-2; // How far (in pixels) the view may be from the pixel grid of its zoom level
+const ORBIT_BYTES : usize = 53; // Size of an orbit in the cache file
+
+/// Position of a tile and the pairs of (index on the screen, index in the tile) of the pixels it shares with the screen
+type Overlap = ((i64, i64), Vec<(usize, usize)>);
+
+/// # `TileKey`
+/// Identifies a tile, the orbits in it are only valid for the same formula and iteration limit
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TileKey {
+    pub formula: String,
+    pub level: i32, // Zoom level, the pixels of level n are half the size of the pixels of level n - 1
+    pub x: i64, // Position in tiles on the grid of the zoom level
+    pub y: i64,
+    pub max_iteration: usize
+}
+
+/// # `Tile`
+/// The orbits of a square of pixels, None for the pixels that were never computed
+struct Tile {
+    orbits: Vec<Option<Orbit>>,
+    last_used: u64 // Value of the cache clock when the tile was last stored or fetched
+}
+
+/// # `Grid`
+/// Where a view lies on the pixel grid of its zoom level
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Grid {
+    pub level: i32,
+    pub x: i64, // Pixel of the grid at the top left corner of the screen
+    pub y: i64
+}
+
+/// # `TileCache`
+/// Keeps the orbits of already computed pixels in tiles so that revisited regions do not have to be computed again.
+/// Only views that are unrotated, at a zoom level (a power of two from the base pixel size) and on its pixel grid can use the cache.
+/// The least recently used tile is dropped when the cache is full
+pub struct TileCache {
+    tiles: HashMap<TileKey, Tile>,
+    capacity: usize, // Most tiles kept at once
+    clock: u64,
+    base_pixel_size: f64 // Size of a pixel at zoom level 0
+}
+
+impl TileCache {
+    /// # `new`
+    /// Creates an empty cache by taking the size of a pixel at zoom level 0 `f64` and the most tiles it can keep `usize`
+    pub fn new(base_pixel_size: f64, capacity: usize) -> TileCache {
+        TileCache {
+            tiles: HashMap::new(),
+            capacity: capacity,
+            clock: 0,
+            base_pixel_size: base_pixel_size
+        }
+    }
+
+    /// # `level`
+    /// Gets the zoom level of the viewport along with the exact size of a pixel at that level, `None` if it is rotated or between levels
+    fn level(&self, viewport: &Viewport<f64>) -> Option<(i32, f64)> {
+        if viewport.rotation != 0.0 {
+            return None;
+        }
+
+        let level = (self.base_pixel_size / viewport.pixel_size()).log2();
+        if (level - level.round()).abs() > LEVEL_TOLERANCE {
+            return None;
+        }
+
+        let level = level.round() as i32;
+        Some((level, self.base_pixel_size / 2f64.powi(level)))
+    }
+
+    /// # `grid`
+    /// Gets where the viewport lies on the pixel grid of its zoom level, `None` if it can not use the cache
+    pub fn grid(&self, viewport: &Viewport<f64>) -> Option<Grid> {
+        let (level, pixel_size) = self.level(viewport)?;
+
+        // Grid pixel at the top left corner, the centre of the screen is half a screen away from it
+        let corner = |centre: f64, screen: f64| centre / pixel_size - screen / 2.0;
+        let (x, y) = (corner(viewport.centre.x, viewport.screen.x), corner(viewport.centre.y, viewport.screen.y));
+        if (x - x.round()).abs() > GRID_TOLERANCE || (y - y.round()).abs() > GRID_TOLERANCE {
+            return None;
+        }
+
+        Some(Grid {
+            level: level,
+            x: x.round() as i64,
+            y: y.round() as i64
+        })
+    }
+
+    /// # `snap`
+    /// Moves an unrotated viewport at a zoom level by less than half a pixel so that it lies on the pixel grid of the level
+    pub fn snap(&self, viewport: &mut Viewport<f64>) {
+        if let Some((_, pixel_size)) = self.level(viewport) {
+            let snapped = |centre: f64, screen: f64| {
+                let corner = (centre / pixel_size - screen / 2.0).round();
+                (corner + screen / 2.0) * pixel_size
+            };
+            viewport.centre = Vector2::new(snapped(viewport.centre.x, viewport.screen.x), snapped(viewport.centre.y, viewport.screen.y));
+        }
+    }
+
+    /// # `overlap`
+    /// Takes the grid of the screen and its size in pixels and returns the tiles the screen covers along with the pixels they have in common
+    fn overlap(grid: Grid, width: usize, height: usize) -> Vec<Overlap> {
+        let size = TILE_SIZE as i64;
+        let (right, bottom) = (grid.x + width as i64, grid.y + height as i64);
+        let mut tiles = Vec::new();
+
+        for ty in grid.y.div_euclid(size)..=(bottom - 1).div_euclid(size) {
+            for tx in grid.x.div_euclid(size)..=(right - 1).div_euclid(size) {
+                let mut pixels = Vec::new();
+                for gy in (ty * size).max(grid.y)..((ty + 1) * size).min(bottom) {
+                    for gx in (tx * size).max(grid.x)..((tx + 1) * size).min(right) {
+                        let screen = (gx - grid.x) as usize + (gy - grid.y) as usize * width;
+                        let local = (gx - tx * size) as usize + (gy - ty * size) as usize * TILE_SIZE;
+                        pixels.push((screen, local));
+                    }
+                }
+                tiles.push(((tx, ty), pixels));
+            }
+        }
+
+        tiles
+    }
+
+    /// # `store`
+    /// Stores the computed `orbits` of a screen of the given size, computed with the `kernel` on the `grid`
+    pub fn store(&mut self, kernel: &Kernel, grid: Grid, orbits: &[Option<Orbit>], width: usize, height: usize) {
+        self.clock += 1;
+        let formula = kernel.formula();
+
+        for ((tx, ty), pixels) in TileCache::overlap(grid, width, height) {
+            if pixels.iter().all(|(screen, _)| orbits[*screen].is_none()) {
+                continue;
+            }
+
+            let key = TileKey {formula: formula.clone(), level: grid.level, x: tx, y: ty, max_iteration: kernel.max_iteration};
+            if !self.tiles.contains_key(&key) && self.tiles.len() >= self.capacity {
+                self.evict();
+            }
+
+            let clock = self.clock;
+            let tile = self.tiles.entry(key).or_insert_with(|| Tile {orbits: vec![None; TILE_SIZE * TILE_SIZE], last_used: clock});
+            tile.last_used = clock;
+            for (screen, local) in pixels {
+                if orbits[screen].is_some() {
+                    tile.orbits[local] = orbits[screen];
+                }
+            }
+        }
+    }
+
+    /// # `fetch`
+    /// Fills the pixels of `orbits` that are not computed yet with the cached orbits of the `kernel` on the `grid`.
+    /// Returns how many pixels were filled
+    pub fn fetch(&mut self, kernel: &Kernel, grid: Grid, orbits: &mut [Option<Orbit>], width: usize, height: usize) -> usize {
+        self.clock += 1;
+        let formula = kernel.formula();
+        let mut filled = 0;
+
+        for ((tx, ty), pixels) in TileCache::overlap(grid, width, height) {
+            let key = TileKey {formula: formula.clone(), level: grid.level, x: tx, y: ty, max_iteration: kernel.max_iteration};
+            if let Some(tile) = self.tiles.get_mut(&key) {
+                tile.last_used = self.clock;
+                for (screen, local) in pixels {
+                    if orbits[screen].is_none() && tile.orbits[local].is_some() {
+                        orbits[screen] = tile.orbits[local];
+                        filled += 1;
+                    }
+                }
+            }
+        }
+
+        filled
+    }
+
+    /// # `evict`
+    /// Drops the least recently used tile
+    fn evict(&mut self) {
+        let oldest = self.tiles.iter()
+            .min_by_key(|(_, tile)| tile.last_used)
+            .map(|(key, _)| key.clone());
+
+        if let Some(key) = oldest {
+            self.tiles.remove(&key);
+        }
+    }
+
+    /// # `save`
+    /// Saves the tiles to the given file, most recently used first
+    pub fn save(&self, path: &str) -> std::io::Result<()> {
+        let mut tiles : Vec<(&TileKey, &Tile)> = self.tiles.iter().collect();
+        tiles.sort_by_key(|(_, tile)| std::cmp::Reverse(tile.last_used));
+
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&(tiles.len() as u64).to_le_bytes());
+        for (key, tile) in tiles {
+            bytes.extend_from_slice(&(key.formula.len() as u32).to_le_bytes());
+            bytes.extend_from_slice(key.formula.as_bytes());
+            bytes.extend_from_slice(&key.level.to_le_bytes());
+            bytes.extend_from_slice(&key.x.to_le_bytes());
+            bytes.extend_from_slice(&key.y.to_le_bytes());
+            bytes.extend_from_slice(&(key.max_iteration as u64).to_le_bytes());
+
+            for orbit in tile.orbits.iter() {
+                match orbit {
+                    Some(orbit) => {
+                        bytes.push(1);
+                        write_orbit(&mut bytes, orbit);
+                    },
+                    None => bytes.push(0)
+                }
+            }
+        }
+
+        File::create(path)?.write_all(&bytes)
+    }
+
+    /// # `load`
+    /// Loads a cache saved with `save` from the given file, keeping at most `capacity` tiles.
+    /// Returns an error if there is no such file or it is not a valid cache file
+    pub fn load(path: &str, base_pixel_size: f64, capacity: usize) -> std::io::Result<TileCache> {
+        let mut bytes = Vec::new();
+        File::open(path)?.read_to_end(&mut bytes)?;
+
+        let mut idx = 0;
+        let mut take = |count: usize| match bytes.get(idx..idx + count) {
+            Some(slice) => {
+                idx += count;
+                Ok(slice)
+            },
+            None => Err(Error::new(ErrorKind::InvalidData, format!("\"{}\": the tile cache is cut short", path)))
+        };
+
+        let mut cache = TileCache::new(base_pixel_size, capacity);
+        let count = u64::from_le_bytes(take(8)?.try_into().unwrap());
+        for _ in 0..count.min(capacity as u64) {
+            let length = u32::from_le_bytes(take(4)?.try_into().unwrap()) as usize;
+            let formula = String::from_utf8_lossy(take(length)?).to_string();
+            let key = TileKey {
+                formula: formula,
+                level: i32::from_le_bytes(take(4)?.try_into().unwrap()),
+                x: i64::from_le_bytes(take(8)?.try_into().unwrap()),
+                y: i64::from_le_bytes(take(8)?.try_into().unwrap()),
+                max_iteration: u64::from_le_bytes(take(8)?.try_into().unwrap()) as usize
+            };
+
+            let mut orbits = Vec::with_capacity(TILE_SIZE * TILE_SIZE);
+            for _ in 0..TILE_SIZE * TILE_SIZE {
+                orbits.push(match take(1)?[0] {
+                    0 => None,
+                    _ => Some(read_orbit(take(ORBIT_BYTES)?))
+                });
+            }
+
+            cache.tiles.insert(key, Tile {orbits: orbits, last_used: 0});
+        }
+
+        Ok(cache)
+    }
+}
+
+/// # `write_orbit`
+/// Appends the `ORBIT_BYTES` bytes of an orbit to `bytes`
+fn write_orbit(bytes: &mut Vec<u8>, orbit: &Orbit) {
+    bytes.extend_from_slice(&(orbit.iterations as u64).to_le_bytes());
+    bytes.push(orbit.escaped as u8);
+    for value in [orbit.z.x, orbit.z.y, orbit.dz.x, orbit.dz.y, orbit.trap_distance, orbit.trap_point.x, orbit.trap_point.y, orbit.angle_sum] {
+        bytes.extend_from_slice(&value.to_le_bytes());
+    }
+    bytes.extend_from_slice(&(orbit.period as u64).to_le_bytes());
+    bytes.extend_from_slice(&orbit.interior_distance.to_le_bytes());
+}
+
+/// # `read_orbit`
+/// Reads an orbit written by `write_orbit` from `ORBIT_BYTES` bytes
+fn read_orbit(bytes: &[u8]) -> Orbit {
+    let float = |idx: usize| f32::from_le_bytes(bytes[idx..idx + 4].try_into().unwrap());
+
+    Orbit {
+        iterations: u64::from_le_bytes(bytes[0..8].try_into().unwrap()) as usize,
+        escaped: bytes[8] != 0,
+        z: Vector2::new(float(9), float(13)),
+        dz: Vector2::new(float(17), float(21)),
+        trap_distance: float(25),
+        trap_point: Vector2::new(float(29), float(33)),
+        angle_sum: float(37),
+        period: u64::from_le_bytes(bytes[41..49].try_into().unwrap()) as usize,
+        interior_distance: float(49)
+    }
+}
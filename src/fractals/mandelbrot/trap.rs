@@ -0,0 +1,53 @@
+use crate::math::vector::Vector2;
+
+// Consts
+const CIRCLE_RADIUS : f32 = 0.5;
+
+/// # `OrbitTrap`
+/// Shapes that the orbits are measured against, a pixel is coloured by how close its orbit comes to the trap
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum OrbitTrap {
+    Point(Vector2),
+    Cross(Vector2), // Two lines through the point, parallel to the axes (Pickover stalks)
+    Circle(Vector2, f32), // Centre and radius
+    Line(Vector2, Vector2) // A point on the line and the direction of the line (of length 1)
+}
+
+impl OrbitTrap {
+    /// # `from_name`
+    /// Gets the trap with the given name (`point`, `cross`, `circle` or `line`) placed at the origin, `None` if there is no such trap
+    pub fn from_name(name: &str) -> Option<OrbitTrap> {
+        let origin = Vector2::new(0.0, 0.0);
+        match name.to_lowercase().trim() {
+            "point" => Some(OrbitTrap::Point(origin)),
+            "cross" => Some(OrbitTrap::Cross(origin)),
+            "circle" => Some(OrbitTrap::Circle(origin, CIRCLE_RADIUS)),
+            "line" => Some(OrbitTrap::Line(origin, Vector2::new(1.0, 0.0))),
+            _ => None
+        }
+    }
+
+    /// # `distance`
+    /// Gets the distance from the point `z` to the trap
+    pub fn distance(&self, z: Vector2) -> f32 {
+        match self {
+            OrbitTrap::Point(point) => (z - *point).norm(),
+            OrbitTrap::Cross(point) => (z.x - point.x).abs().min((z.y - point.y).abs()),
+            OrbitTrap::Circle(centre, radius) => ((z - *centre).norm() - radius).abs(),
+            OrbitTrap::Line(point, direction) => {
+                // The length of the cross product is the distance to the line since the direction has length 1
+                let relative = z - *point;
+                (relative.x * direction.y - relative.y * direction.x).abs()
+            }
+        }
+    }
+
+    /// # `local`
+    /// Gets the point `z` relative to the position of the trap, used to sample images placed on the trap
+    pub fn local(&self, z: Vector2) -> Vector2 {
+        match self {
+            OrbitTrap::Point(point) | OrbitTrap::Cross(point) | OrbitTrap::Line(point, _) => z - *point,
+            OrbitTrap::Circle(centre, _) => z - *centre
+        }
+    }
+}
@@ -4,4 +4,10 @@ pub mod circle_space;
 pub mod koch;
 pub mod sierpinski;
 pub mod tree;
-pub mod mandelbrot;
\ No newline at end of file
+pub mod mandelbrot;
+pub mod lyapunov;
+pub mod buddhabrot;
+pub mod bookmarks;
+pub mod ifs;
+pub mod flame;
+pub mod lsystem;
\ No newline at end of file
@@ -8,6 +8,8 @@ use crate::math::{
     vector::Vector2
 };
 
+use super::Branch;
+
 
 /// # `DiablosTree`
 /// Structure that displays and animates the Rotating Tree Fractal
@@ -44,10 +46,11 @@ impl DiablosTree {
             }, 
             super::DEPTH - depth);
 
-        let next_branch = end - start;
+        let branch_1 = Branch::new(start, end).grow(self.growth_matrix_1);
+        let branch_2 = Branch::new(start, end).grow(self.growth_matrix_2);
 
-        self.branch(end, end + self.growth_matrix_1 * next_branch, depth + 1);
-        self.branch(end, end + self.growth_matrix_2 * next_branch, depth + 1);
+        self.branch(branch_1.start, branch_1.end, depth + 1);
+        self.branch(branch_2.start, branch_2.end, depth + 1);
     }
     
     pub fn draw(&mut self) {
@@ -1,120 +1,113 @@
 use crate::visuals::{
-    visualizer::{WIDTH, HEIGHT, Visualizer},
+    visualizer::Visualizer,
     colours
 };
-use minifb::{MouseButton, MouseMode};
 
 use std::f32::consts::PI;
 
 use crate::math::{
-    matrix::Matrix2,
     vector::Vector2,
-    lsystem::LSystem
+    lsystem::{LSystem, Module},
+    turtle::{WIDTH_FACTOR, LENGTH_FACTOR}
 };
 
 use super::Branch;
 
-const ANGLE : f32 = PI / 7.2;
-const GROWTH : f32 = 0.6;
-const STARTING_LENGTH : f32 = 20.0;
+// Each generation the branches are 0.6 times as long (the first ones are 20 pixels long) and turn by 25 degrees
+const TREE : &str = "
+axiom !(1)F(20/0.6)
+rule F(l) -> -(25)F(l*0.6)+(25)F(l*0.6)+(25)[+(25)F(l*0.6)+(25)F(l*0.6)-(25)F(l*0.6)F(l*0.6)]-(25)[-(25)F(l*0.6)F(l*0.6)-(25)F(l*0.6)+(25)F(l*0.6)]
+";
 
 /// # `LSystemTree`
 /// Structure that generates trees based on the L-System
 pub struct LSystemTree {
     visualizer: Visualizer,
-    branches: Vec<Branch>,
-    rules: LSystem,
-    length: f32
+    rules: LSystem
 }
 
 impl LSystemTree {
     /// # `new`
     /// Creates a new L-System tree fractal
     pub fn new() -> LSystemTree {
-        let mut lsystem = LSystem::new();
-        lsystem.add_rule('F', "-F+F+[+F+F-FF]-[-FF-F+F]".to_string());
         LSystemTree {
             visualizer: Visualizer::new(Some(70)),
-            branches: vec![Branch::new_stem()],
-            rules: lsystem,
-            length: STARTING_LENGTH
+            rules: LSystem::parse(TREE, "lsystem_tree").unwrap()
         }
     }
 
-    /// # `generate`
-    /// Generates the next set of branches based on the given sentence
-    fn generate(&mut self, sentence: &String) {
-        let mut original_matrix = Matrix2::identity(); // Matrix of the whole tree
-        let mut current_branch = self.branches[0]; // Branch currently growing
-        let mut current_matrix = Matrix2::identity(); // Matrix of the current sub-branch
-
-        let mut previous_branches : Vec<Branch> = Vec::new(); // Storing previous main/sub branches
-        let mut previous_matrices : Vec<Matrix2> = Vec::new(); // Storing previous matrices of previous sub branches
-
-        // Rotation matrices
-        let rot_pos = Matrix2::rotation(ANGLE);
-        let rot_neg = rot_pos.inverse().unwrap();
+    /// # `draw_tree`
+    /// Draws the stem and then the tree of the given generation on top of it, starting from the end of the stem.
+    /// The sentence is streamed as it is grown, so deep generations do not have to fit in memory.
+    /// Every branch is drawn along the local x axis and moves the local origin to its end, '[' and ']' save and restore
+    /// the local coordinates on the transform stack of the visualizer. The length, angle and width come from the parameters
+    fn draw_tree(&mut self, generation: usize) {
+        let stem = Branch::new_stem();
+        self.visualizer.reset_transform();
+        let _ = self.visualizer.draw_line(stem.start, stem.end, colours::WHITE, 1);
+        if generation == 0 {
+            return;
+        }
 
-        for character in sentence.chars() {
-            match character {
-                'F' => { // Move forward
-                    let new_branch = Branch::new(current_branch.end, (original_matrix * Vector2::new(0.0, -self.length)) + current_branch.end);
-                    self.branches.push(new_branch);
-                    current_branch = new_branch;
-                },
+        self.rules.iterations = generation;
+        if let Some(count) = self.rules.symbol_count() {
+            self.visualizer.window.set_title(&format!("L-System Tree - generation {} - {} symbols", generation, count));
+        }
 
-                '+' => { // Rotate positively
-                    current_matrix = rot_pos * current_matrix;
-                    original_matrix = rot_pos * original_matrix;
+        // The tree grows upwards, the screen's y axis points down
+        self.visualizer.translate(stem.end);
+        self.visualizer.rotate(-PI / 2.0);
+
+        let (step, angle) = (self.rules.step, self.rules.angle);
+        let (mut width, mut widths) : (f32, Vec<f32>) = (1.0, Vec::new()); // The width is not part of the transform, it is saved alongside it
+        for module in self.rules.stream() {
+            let Module {symbol, parameters} = module;
+            let parameter = parameters.first().copied();
+            match symbol {
+                'F' | 'f' => {
+                    let length = parameter.unwrap_or(step);
+                    if symbol == 'F' {
+                        let _ = self.visualizer.draw_line(Vector2::new(0.0, 0.0), Vector2::new(length, 0.0), colours::WHITE, (width.round() as usize).max(1));
+                    }
+                    self.visualizer.translate(Vector2::new(length, 0.0));
                 },
-
-                '-' => { // Rotate negatively
-                    current_matrix = rot_neg * current_matrix;
-                    original_matrix = rot_neg * original_matrix;
+                '+' => self.visualizer.rotate(parameter.map_or(angle, f32::to_radians)),
+                '-' => self.visualizer.rotate(-parameter.map_or(angle, f32::to_radians)),
+                '|' => self.visualizer.rotate(PI),
+                '[' => {
+                    self.visualizer.push();
+                    widths.push(width);
                 },
-
-                '[' => { // Start a sub branch, store previous data
-                    previous_branches.push(current_branch);
-                    previous_matrices.push(current_matrix);
-                    current_matrix = Matrix2::identity();
-                },
-
-                ']' => { // Exit sub branch, pop previous data
-                    original_matrix = current_matrix.inverse().unwrap() * original_matrix;
-
-                    current_branch = previous_branches.pop().unwrap();
-                    current_matrix = previous_matrices.pop().unwrap();
+                ']' => {
+                    self.visualizer.pop();
+                    width = widths.pop().unwrap_or(width);
                 },
+                '!' => width = parameter.unwrap_or(width * WIDTH_FACTOR),
+                '>' => self.visualizer.scale(parameter.unwrap_or(LENGTH_FACTOR)),
+                '<' => self.visualizer.scale(1.0 / parameter.unwrap_or(LENGTH_FACTOR)),
                 _ => {}
             }
         }
-
-        self.length *= GROWTH; // reduce length each generation
-    } 
+        self.visualizer.reset_transform();
+    }
 
     pub fn draw(&mut self) {
-        let mut sentence : String = "F".to_string();
+        let mut generation = 0; // Only the stem is shown before the first click
 
         // Draw loop, no need for visualizer.end()
         while self.visualizer.window.is_open() {
-            // Draw current branches
-            for branch in self.branches.iter() {
-                self.visualizer.draw_line(branch.start, branch.end, colours::WHITE, 1);
-            }
+            // Draw current tree
+            self.draw_tree(generation);
             self.visualizer.apply_buffer();
 
-            // Clear tree
-            self.branches = vec![Branch::new_stem()];
-
             // Stop until mouse clicked
             self.visualizer.left_pressed();
 
-            // Generate new tree
-            sentence = self.rules.generate(sentence);
-            self.generate(&sentence);
+            // Grow the next generation, the rule makes the branches shorter each generation
+            generation += 1;
 
             // Clean up before new tree drawing
             self.visualizer.clear(None);
         }
     }
-}
\ No newline at end of file
+}
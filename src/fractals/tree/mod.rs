@@ -4,7 +4,8 @@ use super::super::visuals::{
 
 use super::super::math::{
     matrix::Matrix2,
-    vector::Vector2
+    vector::Vector2,
+    transform::Transform2
 };
 
 use std::f32::consts::PI;
@@ -44,13 +45,13 @@ impl Branch {
     }
 
     /// # `grow`
-    /// Creates the next branch by taking the growth matrix `Matrix2`
+    /// Creates the next branch by taking the growth matrix `Matrix2`, the branch is turned and scaled by the matrix and moved to the end of this branch
     pub fn grow(&self, growth_matrix: Matrix2) -> Branch {
-        let next_branch = self.end - self.start;
+        let growth = Transform2::translation(self.end) * Transform2::from_matrix(growth_matrix) * Transform2::translation(-self.start);
 
         Branch {
-            start: self.end,
-            end: self.end + growth_matrix * next_branch,
+            start: growth.transform_point(self.start),
+            end: growth.transform_point(self.end)
         }
     }
 }
@@ -1,6 +1,7 @@
 mod visuals;
 mod fractals;
 mod math;
+mod cli;
 
 fn main()
 {
@@ -19,7 +20,22 @@ fn main()
         "tree" => fractals::tree::simple::SimpleTree::new().draw(),
         "diablos_tree" => fractals::tree::diablos_tree::DiablosTree::new().draw(),
         "lsystem_tree" => fractals::tree::lsystree::LSystemTree::new().draw(),
-        "mandelbrot" => fractals::mandelbrot::Mandelbrot::new().draw(),
+        "lsystem" => fractals::lsystem::LSystemFractal::new(&args).draw(),
+        "mandelbrot" => fractals::mandelbrot::Mandelbrot::new(fractals::mandelbrot::Settings::from_args(&args)).draw(),
+        "buddhabrot" => fractals::buddhabrot::Buddhabrot::new().draw(),
+        "lyapunov" => fractals::lyapunov::Lyapunov::new(args.get(2).map(|s| s.as_str())).draw(),
+        "ifs" => fractals::ifs::Ifs::new(args.get(2).map(|s| s.as_str()).filter(|s| !s.starts_with("--")), cli::has_flag(&args, "--deterministic")).draw(),
+        "flame" => fractals::flame::Flame::new(fractals::flame::Settings::from_args(&args)).draw(),
+        "bookmarks" => fractals::bookmarks::command(&args),
+        "--bookmark" => {
+            let name = args.get(2).unwrap_or_else(|| panic!("Give the name of the bookmark to open"));
+            let bookmark = fractals::bookmarks::Bookmark::find(fractals::bookmarks::BOOKMARK_FILE, name)
+                .unwrap_or_else(|err| panic!("Could not load the bookmark: {}", err));
+            match bookmark.fractal.as_str() {
+                "mandelbrot" => fractals::mandelbrot::Mandelbrot::new(fractals::mandelbrot::Settings::from_args(&args)).draw(),
+                other => panic!("Bookmarks of the {} fractal are not supported", other)
+            }
+        },
         _ => panic!("No such fractal \"{}\"", args[1])
     }
 
@@ -27,9 +43,21 @@ fn main()
 
 #[cfg(test)]
 pub mod tests {
-    use super::math::{matrix::Matrix2, vector::Vector2};
+    use super::math::{matrix::Matrix2, vector::Vector2, random::Random, viewport::Viewport, transform::Transform2, turtle::{Turtle, Segment}, lsystem::{LSystem, Module, parse_sentence}, expression::Expression};
+    use super::fractals::mandelbrot::{kernel::Kernel, keyframes::{Keyframe, Easing}, tiles::TileCache, adaptive_iterations, MAX_ITERATION};
+    use super::fractals::bookmarks::Bookmark;
+    use super::fractals::lyapunov::exponent;
+    use super::fractals::lsystem::{preset, PRESETS as LSYSTEM_PRESETS};
+    use super::fractals::ifs::system::{FunctionSystem, PRESETS};
+    use super::fractals::flame::{system::FlameSystem, variations::Variation};
+    use super::visuals::image::Image;
     use std::f32::consts::PI;
 
+    /// Writes a sentence of modules as text
+    fn sentence_text(sentence: &[Module]) -> String {
+        sentence.iter().map(|module| module.to_string()).collect()
+    }
+
     #[test] // Testing matrix operation such as rotation, inverse and identity (determinant included in inverse)
     fn matrix_operations() {
         let rot = Matrix2::rotation(-PI / 2.0);
@@ -50,4 +78,368 @@ pub mod tests {
         assert_eq!(Vector2::new(1.0, -1.0), e1 - e2);
         assert_eq!(rot * rotinv * e1, e1);
     }
+
+    #[test] // The same operations in f64, along with the dot, cross, normalize, lerp, angle and rotate helpers
+    fn generic_vector_operations() {
+        let e1 : Vector2<f64> = Vector2::new(1.0, 0.0);
+        let e2 : Vector2<f64> = Vector2::new(0.0, 1.0);
+        let rot : Matrix2<f64> = Matrix2::rotation(std::f64::consts::PI / 3.0);
+
+        assert_eq!(Vector2::new(2.0, -1.0), 2.0 * e1 - e2);
+        assert!(((rot * rot.inverse().unwrap() * e2) - e2).norm() < 1E-15);
+        assert_eq!(e1.dot(e2), 0.0);
+        assert_eq!(e1.cross(e2), 1.0);
+        assert_eq!(Vector2::new(3.0, 4.0).normalize(), Vector2::new(0.6, 0.8));
+        assert_eq!(Vector2::<f64>::new(0.0, 0.0).normalize(), Vector2::new(0.0, 0.0));
+        assert_eq!(e1.lerp(e2, 0.25), Vector2::new(0.75, 0.25));
+        assert!((e1.rotate(std::f64::consts::PI / 2.0) - e2).norm() < 1E-15);
+        assert_eq!((-e2).angle(), -std::f64::consts::PI / 2.0);
+    }
+
+    #[test] // Composition applies the right hand side first, directions ignore the translation and the inverse undoes the transform
+    fn transform_operations() {
+        let transform = Transform2::translation(Vector2::new(3.0, -1.0)) * Transform2::rotation(PI / 2.0) * Transform2::scale(2.0, 1.0);
+        let point = Vector2::new(1.0, 1.0);
+
+        assert!((transform.transform_point(point) - Vector2::new(2.0, 1.0)).norm() < 1E-6);
+        assert!((transform.transform_direction(point) - Vector2::new(-1.0, 2.0)).norm() < 1E-6);
+        assert!((transform.inverse().unwrap().transform_point(transform.transform_point(point)) - point).norm() < 1E-6);
+
+        let shear = Transform2::shear(1.0, 0.0) * Transform2::reflection(PI / 4.0);
+        assert!((shear.transform_point(Vector2::new(1.0, 0.0)) - Vector2::new(1.0, 1.0)).norm() < 1E-6);
+        assert_eq!(Transform2::identity().transform_point(point), point);
+        assert_eq!(Transform2::scale(0.0, 1.0).inverse(), None);
+    }
+
+    #[test] // Drawing and moving, turning around, saving and restoring the state, width, colour and step changes
+    fn turtle_commands() {
+        let turtle = Turtle::new(Vector2::new(0.0, 0.0), 0.0, 2.0, PI / 2.0);
+        let segments : Vec<Segment> = turtle.trace(parse_sentence("Ff[+F]|F!'>F<F").unwrap().into_iter()).collect();
+        let close = |segment: Segment, start: Vector2, end: Vector2| (segment.start - start).norm() < 1E-6 && (segment.end - end).norm() < 1E-6;
+
+        assert_eq!(segments.len(), 5);
+        assert!(close(segments[0], Vector2::new(0.0, 0.0), Vector2::new(2.0, 0.0)));
+        assert!(close(segments[1], Vector2::new(4.0, 0.0), Vector2::new(4.0, 2.0)));
+        assert!(close(segments[2], Vector2::new(4.0, 0.0), Vector2::new(2.0, 0.0)));
+        assert!(close(segments[3], Vector2::new(2.0, 0.0), Vector2::new(1.0, 0.0)));
+        assert!(close(segments[4], Vector2::new(1.0, 0.0), Vector2::new(-1.0, 0.0)));
+        assert_eq!((segments[2].width, segments[2].colour), (1.0, 0));
+        assert_eq!((segments[3].width, segments[3].colour), (0.7, 1));
+    }
+
+    #[test] // The Lyapunov exponent must match known values of the logistic map
+    fn lyapunov_exponent() {
+        // Chaotic at r = 4 with exponent ln 2
+        assert!((exponent(&[true], 4.0, 0.0) - 2f32.ln()).abs() < 0.05);
+        // Settles on the fixed point 0.6 at r = 2.5, where f'(0.6) = -0.5
+        assert!((exponent(&[false], 0.0, 2.5) - 0.5f32.ln()).abs() < 1E-3);
+        // Alternating between two rates takes both in turn
+        assert!((exponent(&[true, false], 4.0, 4.0) - 2f32.ln()).abs() < 0.05);
+    }
+
+    #[test] // Same seed must give the same sequence and values must stay within the range
+    fn random_reproducible() {
+        let mut a = Random::new(42);
+        let mut b = Random::new(42);
+
+        for _ in 0..1000 {
+            let value = a.range(-2.0, 2.0);
+            assert_eq!(value, b.range(-2.0, 2.0));
+            assert!((-2.0..2.0).contains(&value));
+        }
+
+        // The seed that scrambles to a state of 0 must not get stuck on 0
+        let mut stuck = Random::new(0x9E3779B97F4A7C15);
+        assert!((0..10).any(|_| stuck.next_u64() != 0));
+        assert!((0..100).any(|_| *stuck.pick(&[1, 2], |_| 1.0) == 2));
+    }
+
+    #[test] // The cardioid/bulb check and periodicity detection must not change a single pixel compared to the plain iteration
+    fn mandelbrot_fast_paths_identical() {
+        let kernel = Kernel {max_iteration: MAX_ITERATION, bailout: 2.0, trap: None, interior: false};
+        for px in 0..200 {
+            for py in 0..150 {
+                let c = Vector2::new(px as f64 / 200.0 * 3.0 - 2.25, py as f64 / 150.0 * 2.25 - 1.125);
+
+                let (mut x, mut y, mut iteration) = (0.0f64, 0.0f64, 0);
+                while x * x + y * y <= 4.0 && iteration < MAX_ITERATION {
+                    let temp = x * x - y * y + c.x;
+                    y = 2.0 * x * y + c.y;
+                    x = temp;
+                    iteration += 1;
+                }
+
+                assert_eq!(kernel.iterate(c).iterations, iteration, "c = {:?}", c);
+            }
+        }
+    }
+
+    #[test] // Loading a plain PPM with a comment and a max value other than 255
+    fn ppm_loading() {
+        let path = std::env::temp_dir().join("fractals_test_image.ppm");
+        std::fs::write(&path, "P3\n# comment\n2 1\n15\n15 0 0  0 15 15\n").unwrap();
+
+        let image = Image::load_ppm(path.to_str().unwrap()).unwrap();
+        assert_eq!((image.width, image.height), (2, 1));
+        assert_eq!(image.pixels, vec![0xFF0000, 0x00FFFF]);
+        assert_eq!(image.sample(0.75, 0.5), 0x00FFFF);
+
+        // An image without pixels has nothing to sample
+        std::fs::write(&path, "P3 0 0 255\n").unwrap();
+        assert!(Image::load_ppm(path.to_str().unwrap()).is_err());
+    }
+
+    #[test] // Points in the main cardioid settle in a 1-cycle and points in the big left bulb in a 2-cycle
+    fn mandelbrot_interior_period() {
+        let kernel = Kernel {max_iteration: MAX_ITERATION, bailout: 2.0, trap: None, interior: true};
+
+        let cardioid = kernel.iterate(Vector2::new(0.1, 0.1));
+        let bulb = kernel.iterate(Vector2::new(-1.05, 0.05));
+
+        assert_eq!((cardioid.escaped, cardioid.period), (false, 1));
+        assert_eq!((bulb.escaped, bulb.period), (false, 2));
+        assert!(cardioid.interior_distance > 0.0 && cardioid.interior_distance.is_finite());
+    }
+
+    #[test] // Scale is interpolated exponentially, so halfway through a zoom the scale is the geometric mean
+    fn keyframe_interpolation() {
+        let start = Keyframe {time: 0.0, centre: Vector2::new(0.0, 0.0), scale: 4.0, rotation: 0.0, palette_offset: 0.0, easing: Easing::Linear};
+        let end = Keyframe {time: 2.0, centre: Vector2::new(1.0, 0.0), scale: 0.04, rotation: 1.0, palette_offset: 10.0, easing: Easing::InOut};
+        let keyframes = [start, end];
+
+        let middle = Keyframe::at(&keyframes, 1.0);
+        assert!((middle.scale - 0.4).abs() < 1E-5);
+        assert!((middle.rotation - 0.5).abs() < 1E-5);
+        assert_eq!(Keyframe::at(&keyframes, -1.0), start);
+        assert_eq!(Keyframe::at(&keyframes, 5.0), end);
+    }
+
+    #[test] // Mapping a pixel to the world and back must give the same pixel, also for rotated views
+    fn viewport_mapping() {
+        let mut viewport = Viewport::new(Vector2::new(-0.5, 0.25), 4.0, Vector2::new(800.0, 600.0));
+        viewport.rotation = PI / 6.0;
+
+        let pixel = Vector2::new(123.0, 456.0);
+        let back = viewport.screen_map().unwrap().world_to_pixel(viewport.pixel_to_world(pixel));
+        assert!((back - pixel).norm() < 1E-3);
+        assert_eq!(viewport.pixel_to_world(Vector2::new(400.0, 300.0)), viewport.centre);
+
+        // A view without size maps every point to the same pixel, so it can not be inverted
+        viewport.scale = 0.0;
+        assert_eq!(viewport.screen_map(), None);
+
+        // Far beyond the precision of f32 the pixels of an f64 view are still apart and map back to themselves
+        let deep = Viewport::<f64>::new(Vector2::new(-0.743643887037151, 0.131825904205330), 1E-11, Vector2::new(800.0, 600.0));
+        let pixel = pixel.cast::<f64>();
+        assert_ne!(deep.pixel_to_world(pixel), deep.pixel_to_world(pixel + Vector2::new(1.0, 0.0)));
+        assert!((deep.screen_map().unwrap().world_to_pixel(deep.pixel_to_world(pixel)) - pixel).norm() < 1E-2);
+    }
+
+    #[test] // Bookmarks must survive a save and load, adding a bookmark with a taken name replaces it and deleting removes it
+    fn bookmark_storage() {
+        let path = std::env::temp_dir().join("fractals_test_bookmarks.txt");
+        let path = path.to_str().unwrap();
+        let _ = std::fs::remove_file(path);
+
+        let bookmark = Bookmark {
+            name: Bookmark::unused_name(path, "mandelbrot").unwrap(), fractal: "mandelbrot".to_string(),
+            centre: Vector2::new(-0.1139083, 0.8990149), scale: 1.5E-4, rotation: 0.25, max_iteration: 500,
+            colouring: "slope".to_string(), interior: "period".to_string(), palette_offset: 12.5
+        };
+        Bookmark::add(path, bookmark.clone()).unwrap();
+        Bookmark::add(path, bookmark.clone()).unwrap();
+
+        assert_eq!(bookmark.name, "mandelbrot_1");
+        assert_eq!(Bookmark::load_all(path).unwrap(), vec![bookmark.clone()]);
+        assert_eq!(Bookmark::unused_name(path, "mandelbrot").unwrap(), "mandelbrot_2");
+        assert!(Bookmark::delete(path, &bookmark.name).unwrap());
+        assert!(Bookmark::find(path, &bookmark.name).is_err());
+    }
+
+    #[test] // Orbits stored for a view must be found again after panning by whole pixels and after saving and loading the cache
+    fn tile_cache_reuse() {
+        let kernel = Kernel {max_iteration: MAX_ITERATION, bailout: 2.0, trap: None, interior: false};
+        let (width, height) = (100, 60);
+        let mut viewport = Viewport::new(Vector2::new(-0.5, 0.0), 0.5, Vector2::new(width as f64, height as f64));
+        let orbits : Vec<_> = (0..width * height)
+            .map(|idx| Some(kernel.iterate(viewport.pixel_to_world(Vector2::from_isize(((idx % width) as isize, (idx / width) as isize))))))
+            .collect();
+
+        let mut cache = TileCache::new(0.005, 16);
+        cache.store(&kernel, cache.grid(&viewport).unwrap(), &orbits, width, height);
+
+        viewport.pan(Vector2::new(10.0, 5.0));
+        let grid = cache.grid(&viewport).unwrap();
+        let mut fetched = vec![None; width * height];
+        assert_eq!(cache.fetch(&kernel, grid, &mut fetched, width, height), 90 * 55);
+        assert_eq!(fetched[0].unwrap().iterations, orbits[10 + 5 * width].unwrap().iterations);
+
+        let path = std::env::temp_dir().join("fractals_test_tiles.cache");
+        cache.save(path.to_str().unwrap()).unwrap();
+        let mut loaded = TileCache::load(path.to_str().unwrap(), 0.005, 16).unwrap();
+        assert_eq!(loaded.fetch(&kernel, grid, &mut vec![None; width * height], width, height), 90 * 55);
+
+        viewport.rotation = 0.1;
+        assert_eq!(cache.grid(&viewport), None);
+    }
+
+    #[test] // The automatic iteration limit starts at MAX_ITERATION and grows by the same amount every time the scale halves
+    fn adaptive_iteration_limit() {
+        assert_eq!(adaptive_iterations(4.0), MAX_ITERATION);
+        assert_eq!(adaptive_iterations(8.0), MAX_ITERATION);
+
+        let steps : Vec<usize> = (1..6).map(|n| adaptive_iterations(4.0 / 2f64.powi(n)) - adaptive_iterations(4.0 / 2f64.powi(n - 1))).collect();
+        assert!(steps[0] > 0 && steps.iter().all(|step| *step == steps[0]));
+    }
+
+    #[test] // Every preset must exist, and maps loaded without a weight are weighted by their determinant
+    fn ifs_loading() {
+        assert!(PRESETS.iter().all(|name| FunctionSystem::preset(name).is_some()));
+
+        let path = std::env::temp_dir().join("fractals_test_system.ifs");
+        std::fs::write(&path, "# Sierpinski carpet corner\n0.5 0 0 0.5 0 0\n\n0.25 0 0 0.5 0.5 0 3\n").unwrap();
+
+        let system = FunctionSystem::load(path.to_str().unwrap()).unwrap();
+        assert_eq!(system.maps.len(), 2);
+        assert_eq!(system.maps[0].weight, 0.25);
+        assert_eq!(system.maps[1].weight, 3.0);
+        assert_eq!(system.maps[1].apply(Vector2::new(2.0, 2.0)), Vector2::new(1.0, 1.0));
+    }
+
+    #[test] // Flame transforms apply their affine map first and then the weighted sum of their variations
+    fn flame_loading() {
+        let path = std::env::temp_dir().join("fractals_test_flame.flame");
+        std::fs::write(&path, "# weight colour a b c d e f variations\n1 0.5  2 0 0 2 0 0  linear 0.5 spherical 0.5\n").unwrap();
+
+        let flame = FlameSystem::load(path.to_str().unwrap()).unwrap();
+        let transform = &flame.transforms[0];
+        assert_eq!(transform.variations, vec![(Variation::Linear, 0.5), (Variation::Spherical, 0.5)]);
+
+        // (1, 0) -> (2, 0), linear gives (2, 0) and spherical (0.5, 0)
+        let point = transform.apply(Vector2::new(1.0, 0.0), &mut Random::new(0));
+        assert!((point - Vector2::new(1.25, 0.0)).norm() < 1E-6);
+
+        std::fs::write(&path, "1 0.5  1 0 0 1 0 0  wobble 1\n").unwrap();
+        assert!(FlameSystem::load(path.to_str().unwrap()).is_err());
+    }
+
+    #[test] // Definition files set the rules and turtle settings, every preset parses and the dragon doubles its segments each iteration
+    fn lsystem_loading() {
+        let mut lsystem = LSystem::parse("# Koch curve\naxiom F\nrule F -> F+F--F+F\nangle 60\niterations 2\nstep 2", "test").unwrap();
+        assert_eq!(sentence_text(&lsystem.expand()), "F+F--F+F+F+F--F+F--F+F--F+F+F+F--F+F");
+        assert_eq!((lsystem.angle, lsystem.step), (60f32.to_radians(), 2.0));
+
+        assert!(LSystem::parse("rule FF -> F", "test").is_err());
+        assert!(LSystem::parse("rule F F", "test").is_err());
+        assert!(LSystem::parse("angle steep", "test").is_err());
+        assert!(LSystem::parse("colour red", "test").is_err());
+
+        for name in LSYSTEM_PRESETS.iter() {
+            assert!(preset(name).is_some(), "{}", name);
+        }
+        let mut dragon = preset("dragon").unwrap();
+        assert_eq!(dragon.expand().iter().filter(|module| module.symbol == 'F').count(), 1 << dragon.iterations);
+    }
+
+    #[test] // Alternative rules are picked in proportion to their weights and the same seed grows the same sentence
+    fn lsystem_stochastic() {
+        let text = "axiom A\nrule A -> AB : 3\nrule A -> AC\niterations 400";
+        let mut a = LSystem::parse(text, "test").unwrap();
+        let mut b = LSystem::parse(text, "test").unwrap();
+        a.seed(7);
+        b.seed(7);
+
+        let sentence = sentence_text(&a.expand());
+        assert_eq!(sentence, sentence_text(&b.expand()));
+        let (bs, cs) = (sentence.matches('B').count(), sentence.matches('C').count());
+        assert_eq!(bs + cs, 400);
+        assert!(bs > 250 && cs > 50, "{} B and {} C", bs, cs);
+
+        assert!(LSystem::parse("rule A -> B : 0", "test").is_err());
+        assert!(LSystem::parse("rule A -> B : heavy", "test").is_err());
+    }
+
+    #[test] // Signals move along the sentence through the left context, and contexts skip ignored characters and sibling branches
+    fn lsystem_context() {
+        let mut signal = LSystem::parse("axiom baaaa\nrule b < a -> b\nrule b -> a\niterations 3", "test").unwrap();
+        assert_eq!(sentence_text(&signal.expand()), "aaaba");
+
+        // The example of The Algorithmic Beauty of Plants, BC < S > G[H]M applies to the S of ABC[DE][SG[HI[JK]L]MNO]
+        let mut branches = LSystem::parse("axiom ABC[DE][SG[HI[JK]L]MNO]\nrule BC < S > G[H]M -> X\nrule S -> Y\niterations 1", "test").unwrap();
+        assert_eq!(sentence_text(&branches.expand()), "ABC[DE][XG[HI[JK]L]MNO]");
+        let mut sibling = LSystem::parse("axiom A[B]C\nrule B < C -> X\nrule A < C -> Y\niterations 1", "test").unwrap();
+        assert_eq!(sentence_text(&sibling.expand()), "A[B]Y");
+
+        let mut ignoring = LSystem::parse("axiom A+F-B\nignore +-F\nrule A < B > * -> C\niterations 1", "test").unwrap();
+        assert_eq!(sentence_text(&ignoring.expand()), "A+F-C");
+    }
+
+    #[test] // Precedence, associativity, comparisons and logic of expressions, and errors for unknown variables
+    fn expression_evaluation() {
+        let evaluate = |text: &str| Expression::parse(text, &["s", "t"]).unwrap().evaluate(&[3.0, 0.5]);
+
+        assert_eq!(evaluate("1 + 2 * s - 4 / 2"), 5.0);
+        assert_eq!(evaluate("(1 + 2) * s"), 9.0);
+        assert_eq!(evaluate("10 - 4 - 3"), 3.0);
+        assert_eq!(evaluate("2 ^ 3 ^ 2"), 512.0);
+        assert_eq!(evaluate("-s ^ 2"), -9.0);
+        assert_eq!(evaluate("s > 1 && t <= 0.5"), 1.0);
+        assert_eq!(evaluate("s == 2 || !(t != 0.5)"), 1.0);
+        assert_eq!(evaluate("s >= 4"), 0.0);
+
+        assert!(Expression::parse("x + 1", &["s"]).is_err());
+        assert!(Expression::parse("(s + 1", &["s"]).is_err());
+        assert!(Expression::parse("s 1", &["s"]).is_err());
+        assert!(Expression::parse("s % 2", &["s"]).is_err());
+    }
+
+    #[test] // Parameters are bound from the predecessor and its contexts, conditions pick the production and the turtle reads the parameters
+    fn lsystem_parametric() {
+        let mut branching = LSystem::parse("axiom A(4)\nrule A(s) : s >= 2 -> F(s)[+(30)A(s/2)]A(s/2)\nrule A(s) : s < 2 -> F(s)\niterations 3", "test").unwrap();
+        assert_eq!(sentence_text(&branching.expand()), "F(4)[+(30)F(2)[+(30)F(1)]F(1)]F(2)[+(30)F(1)]F(1)");
+
+        let mut context = LSystem::parse("axiom B(2)A(1)C(5)\nrule B(x) < A(y) > C(z) : x + y < z -> A(x * y + z)\niterations 1", "test").unwrap();
+        assert_eq!(sentence_text(&context.expand()), "B(2)A(7)C(5)");
+
+        // Productions without named parameters match any parameters, those with names only as many parameters
+        let mut arity = LSystem::parse("axiom A(1)A(1,2)A\nrule A(x, y) -> B(y)\niterations 1", "test").unwrap();
+        assert_eq!(sentence_text(&arity.expand()), "A(1)B(2)A");
+
+        let turtle = Turtle::new(Vector2::new(0.0, 0.0), 0.0, 1.0, 0.0);
+        let segments : Vec<Segment> = turtle.trace(parse_sentence("!(3)F(2)+(90)'(4)F(1 + 1)").unwrap().into_iter()).collect();
+        assert!((segments[1].end - Vector2::new(2.0, 2.0)).norm() < 1E-6);
+        assert_eq!((segments[1].width, segments[1].colour), (3.0, 4));
+
+        assert!(LSystem::parse("rule A(s) -> F(t)", "test").is_err());
+        assert!(LSystem::parse("rule A(1) -> F", "test").is_err());
+        assert!(LSystem::parse("axiom F(s)", "test").is_err());
+        assert!(LSystem::parse("axiom F(1", "test").is_err());
+    }
+
+    #[test] // Streaming gives the same sentence as growing it generation by generation, and the count matches without growing it
+    fn lsystem_streaming() {
+        for name in LSYSTEM_PRESETS.iter() {
+            let mut lsystem = preset(name).unwrap();
+            lsystem.iterations = lsystem.iterations.min(4);
+            lsystem.seed(3);
+            let streamed : Vec<Module> = lsystem.stream().collect();
+            lsystem.seed(3);
+            assert_eq!(streamed, lsystem.stream().collect::<Vec<Module>>(), "{}", name);
+
+            if let Some(count) = lsystem.symbol_count() {
+                assert_eq!(streamed, lsystem.expand(), "{}", name);
+                assert_eq!(count, streamed.len() as u128, "{}", name);
+            }
+        }
+
+        // Deep generations can be counted without growing them
+        let mut dragon = preset("dragon").unwrap();
+        dragon.iterations = 100;
+        assert_eq!(dragon.symbol_count(), Some((1u128 << 102) - 2));
+        assert_eq!(preset("shrub").unwrap().symbol_count(), None);
+
+        // Conditions stop the growth the same way in both
+        let mut branching = preset("branching").unwrap();
+        assert_eq!(branching.stream().collect::<Vec<Module>>(), branching.expand());
+    }
 }
\ No newline at end of file
@@ -0,0 +1,221 @@
+/// # `Operator`
+/// Operators between two expressions, comparisons and logic give 1 for true and 0 for false
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Operator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Power,
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+    Equal,
+    NotEqual,
+    And,
+    Or
+}
+
+impl Operator {
+    /// # `from_token`
+    /// Gets the operator written as `token` and how tightly it binds (higher binds tighter), `None` if it is not an operator
+    fn from_token(token: &str) -> Option<(Operator, u8)> {
+        match token {
+            "||" => Some((Operator::Or, 1)),
+            "&&" => Some((Operator::And, 2)),
+            "==" => Some((Operator::Equal, 3)),
+            "!=" => Some((Operator::NotEqual, 3)),
+            "<" => Some((Operator::Less, 4)),
+            "<=" => Some((Operator::LessEqual, 4)),
+            ">" => Some((Operator::Greater, 4)),
+            ">=" => Some((Operator::GreaterEqual, 4)),
+            "+" => Some((Operator::Add, 5)),
+            "-" => Some((Operator::Subtract, 5)),
+            "*" => Some((Operator::Multiply, 6)),
+            "/" => Some((Operator::Divide, 6)),
+            _ => None
+        }
+    }
+
+    /// # `apply`
+    /// Applies the operator to the two values
+    fn apply(&self, a: f32, b: f32) -> f32 {
+        let truth = |value: bool| if value { 1.0 } else { 0.0 };
+        match self {
+            Operator::Add => a + b,
+            Operator::Subtract => a - b,
+            Operator::Multiply => a * b,
+            Operator::Divide => a / b,
+            Operator::Power => a.powf(b),
+            Operator::Less => truth(a < b),
+            Operator::LessEqual => truth(a <= b),
+            Operator::Greater => truth(a > b),
+            Operator::GreaterEqual => truth(a >= b),
+            Operator::Equal => truth(a == b),
+            Operator::NotEqual => truth(a != b),
+            Operator::And => truth(a != 0.0 && b != 0.0),
+            Operator::Or => truth(a != 0.0 || b != 0.0)
+        }
+    }
+}
+
+/// # `Expression`
+/// A small arithmetic expression over numbers and named variables, such as `s / 1.5` or `t > 2 && s < 10`.
+/// Supports + - * / ^ (power), comparisons, && || ! and parentheses. Conditions are true when they are not 0
+#[derive(Clone, Debug, PartialEq)]
+pub enum Expression {
+    Number(f32),
+    Variable(usize), // Index of the variable in the values given to `evaluate`
+    Negate(Box<Expression>),
+    Not(Box<Expression>),
+    Binary(Operator, Box<Expression>, Box<Expression>)
+}
+
+impl Expression {
+    /// # `parse`
+    /// Reads an expression from the text, the variables it may use are given in the order their values will be given to `evaluate`.
+    /// Returns an error message if the text is not a valid expression or uses an unknown variable
+    pub fn parse(text: &str, variables: &[&str]) -> Result<Expression, String> {
+        let mut parser = Parser {
+            tokens: tokenize(text)?,
+            position: 0,
+            variables: variables
+        };
+
+        let expression = parser.binary(0)?;
+        match parser.tokens.get(parser.position) {
+            Some(token) => Err(format!("unexpected \"{}\" in \"{}\"", token, text)),
+            None => Ok(expression)
+        }
+    }
+
+    /// # `evaluate`
+    /// Computes the value of the expression with the given values of its variables
+    pub fn evaluate(&self, values: &[f32]) -> f32 {
+        match self {
+            Expression::Number(value) => *value,
+            Expression::Variable(index) => values[*index],
+            Expression::Negate(expression) => -expression.evaluate(values),
+            Expression::Not(expression) => if expression.evaluate(values) == 0.0 { 1.0 } else { 0.0 },
+            Expression::Binary(operator, a, b) => operator.apply(a.evaluate(values), b.evaluate(values))
+        }
+    }
+}
+
+/// # `tokenize`
+/// Splits the text into numbers, names, operators and parentheses
+fn tokenize(text: &str) -> Result<Vec<String>, String> {
+    let characters : Vec<char> = text.chars().collect();
+    let mut tokens = Vec::new();
+    let mut index = 0;
+
+    while index < characters.len() {
+        let character = characters[index];
+        let start = index;
+        if character.is_whitespace() {
+            index += 1;
+            continue;
+        }
+        else if character.is_ascii_digit() || character == '.' {
+            while index < characters.len() && (characters[index].is_ascii_digit() || characters[index] == '.') {
+                index += 1;
+            }
+        }
+        else if character.is_alphabetic() || character == '_' {
+            while index < characters.len() && (characters[index].is_alphanumeric() || characters[index] == '_') {
+                index += 1;
+            }
+        }
+        else {
+            // Two character operators first
+            let pair : String = characters[index..(index + 2).min(characters.len())].iter().collect();
+            index += match pair.as_str() {
+                "<=" | ">=" | "==" | "!=" | "&&" | "||" => 2,
+                _ if "+-*/^<>!()".contains(character) => 1,
+                _ => return Err(format!("unexpected '{}' in \"{}\"", character, text))
+            };
+        }
+        tokens.push(characters[start..index].iter().collect());
+    }
+
+    Ok(tokens)
+}
+
+/// # `Parser`
+/// Reads an expression from its tokens by precedence climbing
+struct Parser<'a> {
+    tokens: Vec<String>,
+    position: usize,
+    variables: &'a [&'a str]
+}
+
+impl<'a> Parser<'a> {
+    /// # `next`
+    /// Takes the next token, an error at the end of the expression
+    fn next(&mut self) -> Result<String, String> {
+        let token = self.tokens.get(self.position).cloned().ok_or_else(|| "unexpected end of the expression".to_string())?;
+        self.position += 1;
+        Ok(token)
+    }
+
+    /// # `binary`
+    /// Reads operands joined by operators that bind at least as tightly as `precedence`
+    fn binary(&mut self, precedence: u8) -> Result<Expression, String> {
+        let mut expression = self.unary()?;
+
+        while let Some((operator, binding)) = self.tokens.get(self.position).and_then(|token| Operator::from_token(token)) {
+            if binding < precedence {
+                break;
+            }
+            self.position += 1;
+            // Left associative, the right hand side only takes operators that bind tighter
+            let right = self.binary(binding + 1)?;
+            expression = Expression::Binary(operator, Box::new(expression), Box::new(right));
+        }
+
+        Ok(expression)
+    }
+
+    /// # `unary`
+    /// Reads a negated, inverted or plain power
+    fn unary(&mut self) -> Result<Expression, String> {
+        match self.tokens.get(self.position).map(|token| token.as_str()) {
+            Some("-") => { self.position += 1; Ok(Expression::Negate(Box::new(self.unary()?))) },
+            Some("!") => { self.position += 1; Ok(Expression::Not(Box::new(self.unary()?))) },
+            _ => self.power()
+        }
+    }
+
+    /// # `power`
+    /// Reads a primary raised to a power, which is right associative and binds tighter than negation on its left (-2^2 = -4)
+    fn power(&mut self) -> Result<Expression, String> {
+        let base = self.primary()?;
+        if self.tokens.get(self.position).map(|token| token.as_str()) == Some("^") {
+            self.position += 1;
+            return Ok(Expression::Binary(Operator::Power, Box::new(base), Box::new(self.unary()?)));
+        }
+        Ok(base)
+    }
+
+    /// # `primary`
+    /// Reads a number, a variable or an expression in parentheses
+    fn primary(&mut self) -> Result<Expression, String> {
+        let token = self.next()?;
+        if token == "(" {
+            let expression = self.binary(0)?;
+            return match self.next()?.as_str() {
+                ")" => Ok(expression),
+                other => Err(format!("expected ')' but found \"{}\"", other))
+            };
+        }
+
+        if let Ok(value) = token.parse::<f32>() {
+            return Ok(Expression::Number(value));
+        }
+        match self.variables.iter().position(|variable| *variable == token) {
+            Some(index) => Ok(Expression::Variable(index)),
+            None => Err(format!("unknown variable or unexpected \"{}\"", token))
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use std::ops;
+use std::fmt::Debug;
+
+/// # `Float`
+/// The number type `Vector2` and `Matrix2` are built from. Implemented for `f32` and `f64`,
+/// any other type (such as a double-double or fixed point number) can be used by implementing it
+/// and calling `scalar_ops!` for it so that scalar * vector and scalar * matrix work.
+/// The Mandelbrot set is computed in `f64` so that it can be zoomed deeper, the other fractals use the `f32` default
+pub trait Float:
+    Copy + Debug + PartialEq + PartialOrd
+    + ops::Add<Output = Self> + ops::Sub<Output = Self> + ops::Mul<Output = Self> + ops::Div<Output = Self> + ops::Neg<Output = Self> {
+    fn zero() -> Self;
+    fn one() -> Self;
+    /// Converts a constant, may round
+    fn from_f64(value: f64) -> Self;
+    /// Converts to the widest primitive float, used to convert between `Float` types
+    fn to_f64(self) -> f64;
+    fn sqrt(self) -> Self;
+    fn sin(self) -> Self;
+    fn cos(self) -> Self;
+    /// The angle of the point (x, y) = (other, self) from the x axis, as `f64::atan2`
+    fn atan2(self, other: Self) -> Self;
+}
+
+/// # `float_impl!`
+/// Implements `Float` for a primitive float type by forwarding to its inherent methods
+macro_rules! float_impl {
+    ($($t:ty),*) => {
+        $(
+            impl Float for $t {
+                fn zero() -> $t { 0.0 }
+                fn one() -> $t { 1.0 }
+                fn from_f64(value: f64) -> $t { value as $t }
+                fn to_f64(self) -> f64 { self as f64 }
+                fn sqrt(self) -> $t { <$t>::sqrt(self) }
+                fn sin(self) -> $t { <$t>::sin(self) }
+                fn cos(self) -> $t { <$t>::cos(self) }
+                fn atan2(self, other: $t) -> $t { <$t>::atan2(self, other) }
+            }
+        )*
+    };
+}
+
+/// # `scalar_ops!`
+/// Implements scalar * `Vector2` and scalar * `Matrix2` for the given `Float` types.
+/// These can not be written once for every `Float` since the scalar is on the left hand side
+#[macro_export]
+macro_rules! scalar_ops {
+    ($($t:ty),*) => {
+        $(
+            /// Implement scalar * vector multiplication
+            impl std::ops::Mul<$crate::math::vector::Vector2<$t>> for $t {
+                type Output = $crate::math::vector::Vector2<$t>;
+
+                fn mul(self, _rhs: $crate::math::vector::Vector2<$t>) -> $crate::math::vector::Vector2<$t> {
+                    _rhs * self
+                }
+            }
+
+            /// Implement scalar * Matrix multiplication
+            impl std::ops::Mul<$crate::math::matrix::Matrix2<$t>> for $t {
+                type Output = $crate::math::matrix::Matrix2<$t>;
+
+                fn mul(self, _rhs: $crate::math::matrix::Matrix2<$t>) -> $crate::math::matrix::Matrix2<$t> {
+                    _rhs * self
+                }
+            }
+        )*
+    };
+}
+
+float_impl!(f32, f64);
+scalar_ops!(f32, f64);
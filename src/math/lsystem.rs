@@ -1,9 +1,373 @@
-use std::collections::{HashMap};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::fs::File;
+use std::io::{Error, ErrorKind, Read};
+use super::random::Random;
+use super::expression::Expression;
+
+// Defaults of the definition file
+const AXIOM : &str = "F";
+const ANGLE : f32 = 90.0; // Degrees
+const ITERATIONS : usize = 4;
+const HEADING : f32 = 90.0; // Degrees from the x axis, up
+const STEP : f32 = 1.0;
+
+/// # `Module`
+/// A symbol of a sentence along with its parameters, written as `F` or `F(2.5,1)`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Module {
+    pub symbol: char,
+    pub parameters: Vec<f32>
+}
+
+impl fmt::Display for Module {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.symbol)?;
+        if !self.parameters.is_empty() {
+            let parameters : Vec<String> = self.parameters.iter().map(|parameter| parameter.to_string()).collect();
+            write!(f, "({})", parameters.join(","))?;
+        }
+        Ok(())
+    }
+}
+
+/// # `Pattern`
+/// A module on the left hand side of a production. With named parameters it only matches modules with as many parameters,
+/// which are bound to the names. Without them it matches the symbol with any parameters
+#[derive(Clone, Debug, PartialEq)]
+pub struct Pattern {
+    pub symbol: char,
+    pub arity: Option<usize>
+}
+
+impl Pattern {
+    /// # `parameters`
+    /// The values the pattern binds from the module it matched
+    fn parameters<'a>(&self, module: &'a Module) -> Option<&'a [f32]> {
+        match self.arity {
+            Some(arity) if arity != module.parameters.len() => None,
+            Some(_) => Some(&module.parameters),
+            None => Some(&[])
+        }
+    }
+}
+
+/// # `Template`
+/// A module on the right hand side of a production, its parameters are computed from the values bound by the left hand side
+#[derive(Clone, Debug, PartialEq)]
+pub struct Template {
+    pub symbol: char,
+    pub parameters: Vec<Expression>
+}
+
+impl Template {
+    /// # `instantiate`
+    /// Creates the module with the parameters computed from the bound values
+    fn instantiate(&self, values: &[f32]) -> Module {
+        Module {
+            symbol: self.symbol,
+            parameters: self.parameters.iter().map(|parameter| parameter.evaluate(values)).collect()
+        }
+    }
+}
+
+/// # `Production`
+/// One successor of a symbol. When a symbol has several productions one of them is picked at random every time,
+/// each with a probability proportional to its weight. A production with a left and/or right context only applies
+/// when the symbol is preceded and/or followed by those modules, and a production with a condition only applies when it holds.
+/// The condition and the successor use the parameters bound by the left context, the predecessor and the right context, in that order
+#[derive(Clone, Debug, PartialEq)]
+pub struct Production {
+    pub left: Option<Vec<Pattern>>,
+    pub predecessor: Pattern,
+    pub right: Option<Vec<Pattern>>,
+    pub condition: Option<Expression>,
+    pub successor: Vec<Template>,
+    pub weight: f32
+}
+
+impl Production {
+    /// # `has_context`
+    /// Whether the production only applies within a context
+    pub fn has_context(&self) -> bool {
+        self.left.is_some() || self.right.is_some()
+    }
+
+    /// # `bind`
+    /// Checks whether the production applies to the module at `position` of the sentence, the modules whose symbol is in `ignore`
+    /// are skipped when matching the contexts. Returns the values of the bound parameters if it applies
+    fn bind(&self, sentence: &[Module], position: usize, ignore: &HashSet<char>) -> Option<Vec<f32>> {
+        let mut values = Vec::new();
+
+        if let Some(left) = &self.left {
+            for (pattern, index) in left.iter().zip(left_context(sentence, position, left, ignore)?) {
+                values.extend_from_slice(pattern.parameters(&sentence[index])?);
+            }
+        }
+        values.extend_from_slice(self.predecessor.parameters(&sentence[position])?);
+        if let Some(right) = &self.right {
+            for (pattern, index) in right.iter().zip(right_context(sentence, position + 1, right, ignore)?) {
+                if let Some(index) = index {
+                    values.extend_from_slice(pattern.parameters(&sentence[index])?);
+                }
+            }
+        }
+
+        match &self.condition {
+            Some(condition) if condition.evaluate(&values) == 0.0 => None,
+            _ => Some(values)
+        }
+    }
+}
+
+/// # `left_context`
+/// Finds `context` before `position` in the sentence and returns the indices of the modules it matched, in order.
+/// The context is searched for along the path to the root of the plant: going back over a '[' leaves the branch to its parent,
+/// and branches ending with ']' are siblings which are skipped
+fn left_context(sentence: &[Module], position: usize, context: &[Pattern], ignore: &HashSet<char>) -> Option<Vec<usize>> {
+    let mut index = position;
+    let mut matched = Vec::with_capacity(context.len());
+    for expected in context.iter().rev() {
+        loop {
+            if index == 0 {
+                return None;
+            }
+            match sentence[index - 1].symbol {
+                ']' => index = matching_bracket(sentence, index - 1)?,
+                '[' => index -= 1,
+                symbol if ignore.contains(&symbol) => index -= 1,
+                _ => break
+            }
+        }
+
+        if sentence[index - 1].symbol != expected.symbol {
+            return None;
+        }
+        index -= 1;
+        matched.push(index);
+    }
+
+    matched.reverse();
+    Some(matched)
+}
+
+/// # `right_context`
+/// Finds `context` from `position` on in the sentence and returns the indices of the modules it matched (`None` for a ']' of the context).
+/// Branches starting with '[' are skipped unless the context enters them, a ']' in the context skips the rest of the current branch
+fn right_context(sentence: &[Module], position: usize, context: &[Pattern], ignore: &HashSet<char>) -> Option<Vec<Option<usize>>> {
+    let mut index = position;
+    let mut matched = Vec::with_capacity(context.len());
+    for expected in context.iter() {
+        if expected.symbol == ']' {
+            // Leave the current branch, the context carries on after it
+            index = branch_end(sentence, index)? + 1;
+            matched.push(None);
+            continue;
+        }
+
+        loop {
+            match sentence.get(index)?.symbol {
+                '[' if expected.symbol != '[' => index = matching_bracket(sentence, index)? + 1,
+                symbol if ignore.contains(&symbol) => index += 1,
+                _ => break
+            }
+        }
+
+        if sentence[index].symbol != expected.symbol {
+            return None;
+        }
+        matched.push(Some(index));
+        index += 1;
+    }
+    Some(matched)
+}
+
+/// # `matching_bracket`
+/// Gets the index of the bracket matching the '[' or ']' at `index`, `None` if it is not closed or opened
+fn matching_bracket(sentence: &[Module], index: usize) -> Option<usize> {
+    let mut depth = 0;
+    if sentence[index].symbol == '[' {
+        for (offset, module) in sentence[index..].iter().enumerate() {
+            match module.symbol {
+                '[' => depth += 1,
+                ']' => depth -= 1,
+                _ => {}
+            }
+            if depth == 0 {
+                return Some(index + offset);
+            }
+        }
+    }
+    else {
+        for offset in (0..=index).rev() {
+            match sentence[offset].symbol {
+                ']' => depth += 1,
+                '[' => depth -= 1,
+                _ => {}
+            }
+            if depth == 0 {
+                return Some(offset);
+            }
+        }
+    }
+    None
+}
+
+/// # `branch_end`
+/// Gets the index of the ']' that closes the branch `index` is in, `None` if it is in the main stem
+fn branch_end(sentence: &[Module], index: usize) -> Option<usize> {
+    let mut depth = 0;
+    for (offset, module) in sentence.iter().enumerate().skip(index) {
+        match module.symbol {
+            '[' => depth += 1,
+            ']' if depth == 0 => return Some(offset),
+            ']' => depth -= 1,
+            _ => {}
+        }
+    }
+    None
+}
+
+/// # `Expansion`
+/// Iterator over the modules of a grown sentence. Every module is grown depth first, all the way down before moving on to the next one,
+/// so only the successors on the way to the current module are kept: memory grows with the number of iterations instead of the length of the sentence
+pub struct Expansion<'a> {
+    lsystem: &'a mut LSystem,
+    frames: Vec<(Vec<Module>, usize, usize)> // Modules, index of the next one and iterations left to grow them
+}
+
+impl Iterator for Expansion<'_> {
+    type Item = Module;
+
+    fn next(&mut self) -> Option<Module> {
+        loop {
+            let (modules, index, iterations) = self.frames.last_mut()?;
+            if *index == modules.len() {
+                self.frames.pop();
+                continue;
+            }
+
+            let module = std::mem::replace(&mut modules[*index], Module {symbol: ' ', parameters: Vec::new()});
+            *index += 1;
+            if *iterations == 0 {
+                return Some(module);
+            }
+
+            // A module no production applies to stays the same in every later generation
+            let iterations = *iterations - 1;
+            match self.lsystem.rewrite(std::slice::from_ref(&module), 0) {
+                Some(successor) => self.frames.push((successor, 0, iterations)),
+                None => return Some(module)
+            }
+        }
+    }
+}
+
+/// # `split_modules`
+/// Splits the text of a sentence into its symbols and the text inside the parentheses following them (`None` without parentheses).
+/// Whitespace between modules is skipped
+fn split_modules(text: &str) -> Result<Vec<(char, Option<String>)>, String> {
+    let mut modules = Vec::new();
+    let mut characters = text.chars().filter(|character| !character.is_whitespace()).peekable();
+
+    while let Some(symbol) = characters.next() {
+        if symbol == '(' || symbol == ')' {
+            return Err(format!("unexpected '{}' in \"{}\"", symbol, text));
+        }
+        if characters.peek() != Some(&'(') {
+            modules.push((symbol, None));
+            continue;
+        }
+
+        // Read up to the matching parenthesis, the parameters may have parentheses of their own
+        characters.next();
+        let (mut parameters, mut depth) = (String::new(), 1);
+        loop {
+            let character = characters.next().ok_or_else(|| format!("unclosed parameters of '{}' in \"{}\"", symbol, text))?;
+            match character {
+                '(' => depth += 1,
+                ')' if depth == 1 => break,
+                ')' => depth -= 1,
+                _ => {}
+            }
+            parameters.push(character);
+        }
+        modules.push((symbol, Some(parameters)));
+    }
+
+    Ok(modules)
+}
+
+/// # `split_parameters`
+/// Splits the parameters of a module at the commas that are not inside parentheses
+fn split_parameters(parameters: &str) -> Vec<&str> {
+    let (mut parts, mut depth, mut start) = (Vec::new(), 0, 0);
+    for (index, character) in parameters.char_indices() {
+        match character {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(&parameters[start..index]);
+                start = index + 1;
+            },
+            _ => {}
+        }
+    }
+    parts.push(&parameters[start..]);
+    parts
+}
+
+/// # `parse_patterns`
+/// Reads the modules of the left hand side of a production, such as `A(s,t)B`, along with the names of their parameters
+fn parse_patterns(text: &str) -> Result<Vec<(Pattern, Vec<String>)>, String> {
+    split_modules(text)?.into_iter()
+        .map(|(symbol, parameters)| {
+            let names : Vec<String> = match &parameters {
+                Some(parameters) => split_parameters(parameters).iter().map(|name| name.trim().to_string()).collect(),
+                None => Vec::new()
+            };
+            if names.iter().any(|name| !name.starts_with(|c: char| c.is_alphabetic() || c == '_') || !name.chars().all(|c| c.is_alphanumeric() || c == '_')) {
+                return Err(format!("invalid parameter names of '{}' in \"{}\"", symbol, text));
+            }
+            Ok((Pattern {symbol: symbol, arity: parameters.map(|_| names.len())}, names))
+        })
+        .collect()
+}
+
+/// # `parse_templates`
+/// Reads the modules of the right hand side of a production, such as `F(s)[+A(s/1.5)]`, whose parameters may use the given variables
+fn parse_templates(text: &str, variables: &[&str]) -> Result<Vec<Template>, String> {
+    split_modules(text)?.into_iter()
+        .map(|(symbol, parameters)| Ok(Template {
+            symbol: symbol,
+            parameters: match parameters {
+                Some(parameters) => split_parameters(&parameters).iter()
+                    .map(|parameter| Expression::parse(parameter, variables))
+                    .collect::<Result<Vec<Expression>, String>>()?,
+                None => Vec::new()
+            }
+        }))
+        .collect()
+}
+
+/// # `parse_sentence`
+/// Reads a sentence of modules whose parameters are constant expressions, such as `F(10)A(20/3)`
+pub fn parse_sentence(text: &str) -> Result<Vec<Module>, String> {
+    Ok(parse_templates(text, &[])?.iter().map(|template| template.instantiate(&[])).collect())
+}
 
 /// # `LSystem
-/// Structure that holds rules for the LSystem and generates the next generation by taking input to be grown
+/// Structure that holds rules for the LSystem and generates the next generation by taking input to be grown.
+/// Along with the rules it holds how the system is drawn, the axiom it starts from and how many times it is grown
 pub struct LSystem {
-    rules: HashMap<char, String>
+    rules: HashMap<char, Vec<Production>>,
+    random: Random, // Picks between the productions of a symbol
+    pub ignore: HashSet<char>, // Symbols skipped when matching contexts
+    pub axiom: Vec<Module>,
+    pub angle: f32, // Radians turned by '+' and '-' without a parameter
+    pub iterations: usize,
+    pub heading: f32, // Radians from the x axis the turtle starts facing
+    pub step: f32 // Distance moved by 'F' and 'f' without a parameter
 }
 
 impl LSystem {
@@ -11,27 +375,230 @@ impl LSystem {
     /// Create a new L-System with no rules
     pub fn new() -> LSystem {
         LSystem {
-            rules: HashMap::new()
+            rules: HashMap::new(),
+            random: Random::from_time(),
+            ignore: HashSet::new(),
+            axiom: parse_sentence(AXIOM).unwrap(),
+            angle: ANGLE.to_radians(),
+            iterations: ITERATIONS,
+            heading: HEADING.to_radians(),
+            step: STEP
         }
     }
 
-    /// # `add_rule`
-    /// Add a new rule to the LSystem by taking a character `char` and what that character would become `String`
-    pub fn add_rule(&mut self, character: char, becomes: String) {
-        self.rules.insert(character, becomes);
+    /// # `load`
+    /// Loads an L-System from a definition file (see `parse`). Returns an error if the file can not be read or is invalid
+    pub fn load(path: &str) -> std::io::Result<LSystem> {
+        let mut text = String::new();
+        File::open(path)?.read_to_string(&mut text)?;
+        LSystem::parse(&text, path)
+    }
+
+    /// # `parse`
+    /// Reads an L-System from the text of a definition file, `source` is the name of the file used in the errors.
+    /// Every line is a keyword followed by its value:
+    /// - `axiom <sentence>` the sentence the system starts from, parameters may be constant expressions such as `A(20/3)`
+    /// - `rule <symbol> -> <sentence> [: <weight>]` a production, the sentence may be empty.
+    ///   A symbol with several productions picks one at random each time, in proportion to their weights (default 1).
+    ///   The symbol may be given a context as `<left> < <symbol> > <right>` (either side can be left out or be `*`),
+    ///   productions whose context matches take precedence over those without a context.
+    ///   Modules may name their parameters, as in `A(s) : s > 1 -> F(s)[+A(s/1.5)]`, the condition after ':' decides when
+    ///   the production applies and the parameters of the successor are computed from the named ones
+    /// - `ignore <symbols>` the symbols skipped when matching contexts, such as `+-F`
+    /// - `angle <degrees>`, `iterations <n>`, `heading <degrees>` and `step <length>` for the turtle
+    ///
+    /// Empty lines and lines starting with '#' are skipped, keywords that are left out keep their defaults
+    pub fn parse(text: &str, source: &str) -> std::io::Result<LSystem> {
+        let mut lsystem = LSystem::new();
+        for (number, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let invalid = |message: &str| Error::new(ErrorKind::InvalidData, format!("\"{}\" line {}: {}", source, number + 1, message));
+            let (keyword, value) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+            let value = value.trim();
+            let number = |value: &str| value.parse::<f32>().map_err(|_| invalid("invalid number"));
+
+            match keyword {
+                "axiom" => lsystem.axiom = parse_sentence(value).map_err(|err| invalid(&err))?,
+                "rule" => {
+                    let production = LSystem::parse_production(value).map_err(|err| invalid(&err))?;
+                    if production.weight <= 0.0 {
+                        return Err(invalid("the weight must be positive"));
+                    }
+                    lsystem.add_production(production);
+                },
+                "ignore" => lsystem.ignore = value.chars().filter(|character| !character.is_whitespace()).collect(),
+                "angle" => lsystem.angle = number(value)?.to_radians(),
+                "iterations" => lsystem.iterations = value.parse::<usize>().map_err(|_| invalid("invalid number of iterations"))?,
+                "heading" => lsystem.heading = number(value)?.to_radians(),
+                "step" => lsystem.step = number(value)?,
+                _ => return Err(invalid("unknown keyword, expected axiom, rule, ignore, angle, iterations, heading or step"))
+            }
+        }
+
+        Ok(lsystem)
+    }
+
+    /// # `parse_production`
+    /// Reads a production written as `[<left> <] <symbol> [> <right>] [: <condition>] -> <sentence> [: <weight>]`
+    fn parse_production(text: &str) -> Result<Production, String> {
+        let (predecessor, successor) = text.split_once("->").ok_or_else(|| "expected <symbol> -> <sentence>".to_string())?;
+        let (successor, weight) = match successor.split_once(':') {
+            Some((successor, weight)) => (successor, weight.trim().parse::<f32>().map_err(|_| "invalid weight".to_string())?),
+            None => (successor, 1.0)
+        };
+        let (predecessor, condition) = match predecessor.split_once(':') {
+            Some((predecessor, condition)) => (predecessor, Some(condition)),
+            None => (predecessor, None)
+        };
+
+        // Split off the contexts, a '*' matches anything
+        let context = |context: &str| match context.trim() {
+            "*" | "" => Ok(None),
+            context => parse_patterns(context).map(Some)
+        };
+        let (left, predecessor) = match predecessor.split_once(" < ") {
+            Some((left, predecessor)) => (context(left)?, predecessor),
+            None => (None, predecessor)
+        };
+        let (predecessor, right) = match predecessor.split_once(" > ") {
+            Some((predecessor, right)) => (predecessor, context(right)?),
+            None => (predecessor, None)
+        };
+        let mut predecessor = parse_patterns(predecessor)?;
+        if predecessor.len() != 1 {
+            return Err("a rule replaces a single symbol".to_string());
+        }
+        let (predecessor, names) = predecessor.remove(0);
+
+        // The parameters are named from the left context to the right context
+        let empty = Vec::new();
+        let names : Vec<&str> = left.as_ref().unwrap_or(&empty).iter().flat_map(|(_, names)| names.iter())
+            .chain(names.iter())
+            .chain(right.as_ref().unwrap_or(&empty).iter().flat_map(|(_, names)| names.iter()))
+            .map(|name| name.as_str())
+            .collect();
+        let patterns = |context: Option<Vec<(Pattern, Vec<String>)>>| context.map(|context| context.into_iter().map(|(pattern, _)| pattern).collect());
+
+        Ok(Production {
+            condition: condition.map(|condition| Expression::parse(condition, &names)).transpose()?,
+            successor: parse_templates(successor, &names)?,
+            left: patterns(left),
+            predecessor: predecessor,
+            right: patterns(right),
+            weight: weight
+        })
+    }
+
+    /// # `add_production`
+    /// Add a new production, which may have a context and a condition, to the rules of its symbol.
+    /// If the symbol already has rules this becomes another alternative, picked in proportion to its weight
+    pub fn add_production(&mut self, production: Production) {
+        self.rules.entry(production.predecessor.symbol).or_default().push(production);
+    }
+
+    /// # `seed`
+    /// Reseeds the generator that picks between alternative rules, the same seed always grows the same sentences
+    pub fn seed(&mut self, seed: u64) {
+        self.random = Random::new(seed);
+    }
+
+    /// # `rewrite`
+    /// Returns what the module at `position` of the sentence becomes, `None` if no production applies to it
+    fn rewrite(&mut self, sentence: &[Module], position: usize) -> Option<Vec<Module>> {
+        let productions = self.rules.get(&sentence[position].symbol)?;
+
+        // Productions with a matching context take precedence over the context free ones
+        let matching : Vec<(&Production, Vec<f32>)> = productions.iter()
+            .filter_map(|production| production.bind(sentence, position, &self.ignore).map(|values| (production, values)))
+            .collect();
+        let contextual = matching.iter().any(|(production, _)| production.has_context());
+        let candidates : Vec<(&Production, Vec<f32>)> = matching.into_iter()
+            .filter(|(production, _)| production.has_context() == contextual)
+            .collect();
+
+        if candidates.is_empty() {
+            return None;
+        }
+        let (production, values) = self.random.pick(&candidates, |(production, _)| production.weight);
+        Some(production.successor.iter().map(|template| template.instantiate(values)).collect())
     }
 
     /// # `generate`
-    /// Takes a `String` and returns the string after one iteration of the L-System
-    pub fn generate(&self, input: String) -> String {
-        let mut output = String::new();
-        for character in input.chars() {
-            match self.rules.get(&character) {
-                Some(a) => output.push_str(a),
-                _ => output.push(character)
-            };
+    /// Takes a sentence and returns the sentence after one iteration of the L-System
+    pub fn generate(&mut self, sentence: &[Module]) -> Vec<Module> {
+        let mut output = Vec::with_capacity(sentence.len());
+        for (position, module) in sentence.iter().enumerate() {
+            match self.rewrite(sentence, position) {
+                Some(successor) => output.extend(successor),
+                None => output.push(module.clone())
+            }
         }
 
         output
     }
-}
\ No newline at end of file
+
+    /// # `has_contexts`
+    /// Whether any production has a context, those need the whole sentence to be grown
+    fn has_contexts(&self) -> bool {
+        self.rules.values().flatten().any(|production| production.has_context())
+    }
+
+    /// # `stream`
+    /// Returns the modules of the sentence grown `iterations` times one by one, without building the sentence (see `Expansion`).
+    /// Productions with contexts need the neighbours of every module, so systems with contexts are grown with `expand` instead
+    pub fn stream(&mut self) -> Expansion<'_> {
+        let frame = match self.has_contexts() {
+            true => (self.expand(), 0, 0),
+            false => (self.axiom.clone(), 0, self.iterations)
+        };
+        Expansion {
+            lsystem: self,
+            frames: vec![frame]
+        }
+    }
+
+    /// # `symbol_count`
+    /// Counts the modules of the sentence grown `iterations` times without growing it, by counting how many modules every symbol
+    /// becomes generation by generation. This is only possible when no symbol has a choice of productions, a context or a condition, `None` otherwise.
+    /// Counts too large for a `u128` are capped
+    pub fn symbol_count(&self) -> Option<u128> {
+        if self.rules.values().any(|productions| productions.len() > 1 || productions.iter().any(|production| production.has_context() || production.condition.is_some())) {
+            return None;
+        }
+
+        // Modules are told apart by their symbol and number of parameters, which decide whether a production matches them
+        let mut kinds : HashSet<(char, usize)> = self.axiom.iter().map(|module| (module.symbol, module.parameters.len())).collect();
+        kinds.extend(self.rules.values().flatten().flat_map(|production| production.successor.iter()).map(|template| (template.symbol, template.parameters.len())));
+        let production = |(symbol, arity): (char, usize)| self.rules.get(&symbol)
+            .and_then(|productions| productions.first())
+            .filter(|production| production.predecessor.arity.unwrap_or(arity) == arity);
+
+        let mut counts : HashMap<(char, usize), u128> = kinds.iter().map(|kind| (*kind, 1)).collect();
+        for _ in 0..self.iterations {
+            counts = kinds.iter()
+                .map(|kind| (*kind, match production(*kind) {
+                    Some(production) => production.successor.iter()
+                        .map(|template| counts[&(template.symbol, template.parameters.len())])
+                        .fold(0u128, u128::saturating_add),
+                    None => 1
+                }))
+                .collect();
+        }
+
+        Some(self.axiom.iter().map(|module| counts[&(module.symbol, module.parameters.len())]).fold(0u128, u128::saturating_add))
+    }
+
+    /// # `expand`
+    /// Returns the sentence after growing the axiom `iterations` times
+    pub fn expand(&mut self) -> Vec<Module> {
+        let mut sentence = self.axiom.clone();
+        for _ in 0..self.iterations {
+            sentence = self.generate(&sentence);
+        }
+        sentence
+    }
+}
@@ -1,30 +1,31 @@
 use std::ops;
+use super::float::Float;
 
 #[derive(Debug, PartialEq, Copy, Clone)]
 /// # `Matrix2`
-/// A good ol' 2x2 Matrix
-pub struct Matrix2 {
-    pub c11: f32,
-    pub c12: f32,
-    pub c21: f32,
-    pub c22: f32,
+/// A good ol' 2x2 Matrix, of `f32` unless another `Float` type is given
+pub struct Matrix2<T = f32> {
+    pub c11: T,
+    pub c12: T,
+    pub c21: T,
+    pub c22: T,
 }
 
-impl Matrix2 {
+impl<T: Float> Matrix2<T> {
     /// # `identity`
     /// Creates an identity 2x2 matrix
-    pub fn identity() -> Matrix2 {
+    pub fn identity() -> Matrix2<T> {
         Matrix2 {
-            c11: 1.0,
-            c12: 0.0,
-            c21: 0.0,
-            c22: 1.0
+            c11: T::one(),
+            c12: T::zero(),
+            c21: T::zero(),
+            c22: T::one()
         }
     }
 
     /// # `rotation`
-    /// Creates a rotation matrix using the given angle `f32` in radians
-    pub fn rotation(radians: f32) -> Matrix2 {
+    /// Creates a rotation matrix using the given angle in radians
+    pub fn rotation(radians: T) -> Matrix2<T> {
         Matrix2 {
             c11: radians.cos(),
             c21: radians.sin(),
@@ -34,46 +35,46 @@ impl Matrix2 {
     }
 
     /// # `determinant`
-    /// Returns the determinant of the matrix as an `f32`
-    pub fn determinant(&self) -> f32 {
+    /// Returns the determinant of the matrix
+    pub fn determinant(&self) -> T {
         self.c11 * self.c22 - self.c12 * self.c21
     }
 
     /// # `inverse`
     /// Returns the inverse of the matrix
-    pub fn inverse(&self) -> Option<Matrix2> {
+    pub fn inverse(&self) -> Option<Matrix2<T>> {
         let det = self.determinant();
 
-        if det != 0.0 {
-            return Some((1.0 / det) * Matrix2 {
+        if det != T::zero() {
+            return Some(Matrix2 {
                 c11: self.c22,
                 c12: -self.c12,
                 c21: -self.c21,
-                c22: self.c11 });
+                c22: self.c11 } * (T::one() / det));
         }
         None
     }
 }
 
-/// Implement scalar * Matrix multiplication
-impl ops::Mul<Matrix2> for f32 {
-    type Output = Matrix2;
+/// Implement Matrix * scalar multiplication, scalar * Matrix is implemented by `scalar_ops!`
+impl<T: Float> ops::Mul<T> for Matrix2<T> {
+    type Output = Matrix2<T>;
 
-    fn mul(self, _rhs: Matrix2) -> Matrix2 {
+    fn mul(self, _rhs: T) -> Matrix2<T> {
         Matrix2 {
-            c11: _rhs.c11 * self,
-            c12: _rhs.c12 * self,
-            c21: _rhs.c21 * self,
-            c22: _rhs.c22 * self
+            c11: self.c11 * _rhs,
+            c12: self.c12 * _rhs,
+            c21: self.c21 * _rhs,
+            c22: self.c22 * _rhs
         }
     }
 }
 
 /// Implement Matrix * Matrix multiplication
-impl ops::Mul<Matrix2> for Matrix2 {
-    type Output = Matrix2;
+impl<T: Float> ops::Mul<Matrix2<T>> for Matrix2<T> {
+    type Output = Matrix2<T>;
 
-    fn mul(self, _rhs: Matrix2) -> Matrix2{
+    fn mul(self, _rhs: Matrix2<T>) -> Matrix2<T>{
         Matrix2 {
             c11: self.c11 * _rhs.c11 + self.c12 * _rhs.c21,
             c12: self.c11 * _rhs.c12 + self.c12 * _rhs.c22,
@@ -1,3 +1,9 @@
+pub mod float;
 pub mod matrix;
 pub mod vector;
-pub mod lsystem;
\ No newline at end of file
+pub mod lsystem;
+pub mod random;
+pub mod viewport;
+pub mod transform;
+pub mod turtle;
+pub mod expression;
\ No newline at end of file
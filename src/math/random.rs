@@ -0,0 +1,68 @@
+const SCRAMBLE : u64 = 0x9E3779B97F4A7C15; // Mixed into the seed so that small seeds do not start from a nearly empty state
+
+/// # `Random`
+/// A small seedable pseudo random number generator (xorshift64*), good enough for sampling fractals
+pub struct Random {
+    state: u64
+}
+
+impl Random {
+    /// # `new`
+    /// Creates a new generator from the given seed `u64`. The same seed always gives the same sequence of numbers
+    pub fn new(seed: u64) -> Random {
+        // The state must never be 0, otherwise the generator only ever returns 0. Only one seed scrambles to 0, it gets another state
+        let state = seed ^ SCRAMBLE;
+        Random {
+            state: if state == 0 { SCRAMBLE } else { state }
+        }
+    }
+
+    /// # `from_time`
+    /// Creates a new generator seeded by the current system time
+    pub fn from_time() -> Random {
+        let seed = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or(0);
+
+        Random::new(seed)
+    }
+
+    /// # `next_u64`
+    /// Returns the next random `u64`
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545F4914F6CDD1D)
+    }
+
+    /// # `next_f32`
+    /// Returns a random `f32` in the range [0, 1)
+    pub fn next_f32(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+    }
+
+    /// # `range`
+    /// Returns a random `f32` in the range [`min`, `max`)
+    pub fn range(&mut self, min: f32, max: f32) -> f32 {
+        min + (max - min) * self.next_f32()
+    }
+
+    /// # `pick`
+    /// Picks a random item of a non-empty slice, each with a probability proportional to the (non-negative) weight given by `weight`
+    pub fn pick<'a, T>(&mut self, items: &'a [T], weight: impl Fn(&T) -> f32) -> &'a T {
+        let total : f32 = items.iter().map(&weight).sum();
+        let mut pick = self.range(0.0, total);
+
+        for item in items.iter() {
+            if pick < weight(item) {
+                return item;
+            }
+            pick -= weight(item);
+        }
+
+        // Only reached through rounding errors
+        items.iter().rev().find(|item| weight(item) > 0.0).unwrap_or(&items[items.len() - 1])
+    }
+}
@@ -0,0 +1,104 @@
+use std::ops;
+use super::{
+    matrix::Matrix2,
+    vector::Vector2,
+    float::Float
+};
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+/// # `Transform2`
+/// An affine transform of the plane, p -> Mp + t. Behaves like the 3x3 homogeneous matrix
+/// | M t |
+/// | 0 1 |
+/// so points (w = 1) are moved by the translation while directions (w = 0) are not. Of `f32` unless another `Float` type is given
+pub struct Transform2<T = f32> {
+    pub matrix: Matrix2<T>,
+    pub translation: Vector2<T>
+}
+
+impl<T: Float> Transform2<T> {
+    /// # `new`
+    /// Creates the transform that applies the linear map `matrix` and then moves by `translation`
+    pub fn new(matrix: Matrix2<T>, translation: Vector2<T>) -> Transform2<T> {
+        Transform2 {
+            matrix: matrix,
+            translation: translation
+        }
+    }
+
+    /// # `identity`
+    /// Creates the transform that leaves everything in place
+    pub fn identity() -> Transform2<T> {
+        Transform2::from_matrix(Matrix2::identity())
+    }
+
+    /// # `from_matrix`
+    /// Creates the transform of a linear map, without translation
+    pub fn from_matrix(matrix: Matrix2<T>) -> Transform2<T> {
+        Transform2::new(matrix, Vector2::new(T::zero(), T::zero()))
+    }
+
+    /// # `translation`
+    /// Creates a transform that moves everything by `offset`
+    pub fn translation(offset: Vector2<T>) -> Transform2<T> {
+        Transform2::new(Matrix2::identity(), offset)
+    }
+
+    /// # `rotation`
+    /// Creates a rotation around the origin by the given angle in radians
+    pub fn rotation(radians: T) -> Transform2<T> {
+        Transform2::from_matrix(Matrix2::rotation(radians))
+    }
+
+    /// # `scale`
+    /// Creates a scaling from the origin by `x` along the x axis and `y` along the y axis
+    pub fn scale(x: T, y: T) -> Transform2<T> {
+        Transform2::from_matrix(Matrix2 {c11: x, c12: T::zero(), c21: T::zero(), c22: y})
+    }
+
+    /// # `shear`
+    /// Creates a shear that moves x by `x` times y and y by `y` times x
+    #[allow(dead_code)] // Part of the transform toolkit, no fractal shears or reflects yet
+    pub fn shear(x: T, y: T) -> Transform2<T> {
+        Transform2::from_matrix(Matrix2 {c11: T::one(), c12: x, c21: y, c22: T::one()})
+    }
+
+    /// # `reflection`
+    /// Creates a reflection in the line through the origin at the given angle in radians from the x axis
+    #[allow(dead_code)] // Part of the transform toolkit, no fractal shears or reflects yet
+    pub fn reflection(radians: T) -> Transform2<T> {
+        let (sin, cos) = ((radians + radians).sin(), (radians + radians).cos());
+        Transform2::from_matrix(Matrix2 {c11: cos, c12: sin, c21: sin, c22: -cos})
+    }
+
+    /// # `inverse`
+    /// Returns the transform that undoes this one, `None` if the linear part can not be inverted
+    pub fn inverse(&self) -> Option<Transform2<T>> {
+        let matrix = self.matrix.inverse()?;
+        Some(Transform2::new(matrix, -(matrix * self.translation)))
+    }
+
+    /// # `transform_point`
+    /// Maps a position, it is both transformed by the linear part and moved by the translation
+    pub fn transform_point(&self, point: Vector2<T>) -> Vector2<T> {
+        self.matrix * point + self.translation
+    }
+
+    /// # `transform_direction`
+    /// Maps a direction (or the difference of two points), only the linear part applies to it
+    pub fn transform_direction(&self, direction: Vector2<T>) -> Vector2<T> {
+        self.matrix * direction
+    }
+}
+
+/// Implement Transform * Transform composition, the right hand side is applied first
+impl<T: Float> ops::Mul<Transform2<T>> for Transform2<T> {
+    type Output = Transform2<T>;
+
+    fn mul(self, _rhs: Transform2<T>) -> Transform2<T> {
+        Transform2 {
+            matrix: self.matrix * _rhs.matrix,
+            translation: self.matrix * _rhs.translation + self.translation
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use super::vector::Vector2;
+use super::lsystem::Module;
+
+// Defaults
+const WIDTH : f32 = 1.0;
+pub const WIDTH_FACTOR : f32 = 0.7; // Width is multiplied by this on '!'
+pub const LENGTH_FACTOR : f32 = 0.5; // Step is multiplied by this on '>' and divided by it on '<'
+
+/// # `Segment`
+/// A line drawn by the turtle, with the width and colour index the turtle had when drawing it
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct Segment {
+    pub start: Vector2,
+    pub end: Vector2,
+    pub width: f32,
+    pub colour: usize
+}
+
+/// # `State`
+/// Everything about the turtle that is saved by '[' and restored by ']'
+#[derive(Debug, Copy, Clone)]
+struct State {
+    position: Vector2,
+    heading: f32, // Radians from the x axis
+    step: f32,
+    width: f32,
+    colour: usize
+}
+
+/// # `Turtle`
+/// Interprets L-system sentences as turtle graphics, one module at a time, and returns the segments it draws:
+/// - `F` moves forward by the step while drawing, `f` moves without drawing
+/// - `+` and `-` turn by the angle (positive and negative), `|` turns around
+/// - `[` saves the state of the turtle and `]` goes back to the last saved state
+/// - `!` makes the lines narrower by the width factor, `'` moves on to the next colour index
+/// - `>` makes the step shorter by the length factor and `<` makes it longer
+///
+/// A module with a parameter uses it instead: `F(l)` and `f(l)` move by l, `+(a)` and `-(a)` turn by a degrees,
+/// `!(w)` sets the width to w, `'(c)` sets the colour index to c and `>(x)` and `<(x)` multiply and divide the step by x.
+/// Other symbols are ignored
+pub struct Turtle {
+    pub angle: f32, // Radians turned by '+' and '-'
+    pub width_factor: f32,
+    pub length_factor: f32,
+    state: State,
+    stack: Vec<State>
+}
+
+impl Turtle {
+    /// # `new`
+    /// Creates a turtle at `position` facing `heading` (radians from the x axis) that moves by `step` and turns by `angle` radians
+    pub fn new(position: Vector2, heading: f32, step: f32, angle: f32) -> Turtle {
+        Turtle {
+            angle: angle,
+            width_factor: WIDTH_FACTOR,
+            length_factor: LENGTH_FACTOR,
+            state: State {
+                position: position,
+                heading: heading,
+                step: step,
+                width: WIDTH,
+                colour: 0
+            },
+            stack: Vec::new()
+        }
+    }
+
+    /// # `execute`
+    /// Carries out a single module, returns the segment drawn if the module draws one
+    pub fn execute(&mut self, module: &Module) -> Option<Segment> {
+        let state = &mut self.state;
+        let parameter = module.parameters.first().copied();
+        match module.symbol {
+            'F' | 'f' => {
+                let start = state.position;
+                state.position = start + Vector2::new(state.heading.cos(), state.heading.sin()) * parameter.unwrap_or(state.step);
+                if module.symbol == 'F' {
+                    return Some(Segment {start: start, end: state.position, width: state.width, colour: state.colour});
+                }
+            },
+            '+' => state.heading += parameter.map_or(self.angle, f32::to_radians),
+            '-' => state.heading -= parameter.map_or(self.angle, f32::to_radians),
+            '|' => state.heading += std::f32::consts::PI,
+            '[' => self.stack.push(*state),
+            ']' => if let Some(saved) = self.stack.pop() { *state = saved },
+            '!' => state.width = parameter.unwrap_or(state.width * self.width_factor),
+            '\'' => state.colour = parameter.map_or(state.colour + 1, |colour| colour.max(0.0) as usize),
+            '>' => state.step *= parameter.unwrap_or(self.length_factor),
+            '<' => state.step /= parameter.unwrap_or(self.length_factor),
+            _ => {}
+        }
+        None
+    }
+
+    /// # `trace`
+    /// Carries out the modules as they come and yields the segments drawn, in order. The modules can be streamed
+    /// straight from `LSystem::stream` so the sentence is never stored
+    pub fn trace<I: Iterator<Item = Module>>(mut self, modules: I) -> impl Iterator<Item = Segment> {
+        modules.filter_map(move |module| self.execute(&module))
+    }
+}
@@ -1,18 +1,19 @@
 use std::ops;
 use super::matrix::Matrix2;
+use super::float::Float;
 
 #[derive(Debug, PartialEq, Copy, Clone)]
 /// # `Vector`
-/// Structure that stores a 2D vector
-pub struct Vector2 {
-    pub x: f32,
-    pub y: f32
+/// Structure that stores a 2D vector, the coordinates are `f32` unless another `Float` type is given
+pub struct Vector2<T = f32> {
+    pub x: T,
+    pub y: T
 }
 
-impl Vector2 {
+impl<T: Float> Vector2<T> {
     /// # `new`
-    /// Takes a `f32` x and y coordinate and returns a Vector with those coordinates
-    pub fn new(x: f32, y: f32) -> Vector2 {
+    /// Takes an x and y coordinate and returns a Vector with those coordinates
+    pub fn new(x: T, y: T) -> Vector2<T> {
         Vector2 {
             x: x,
             y: y
@@ -21,20 +22,86 @@ impl Vector2 {
 
     /// # `from_usize`
     /// Takes a `(isize, isize)` coordinates and returns a Vector with those coordinates
-    pub fn from_isize(coordinates: (isize, isize)) -> Vector2 {
-        Vector2::new(coordinates.0 as f32, coordinates.1 as f32)
+    pub fn from_isize(coordinates: (isize, isize)) -> Vector2<T> {
+        Vector2::new(T::from_f64(coordinates.0 as f64), T::from_f64(coordinates.1 as f64))
     }
 
-    pub fn norm(&self) -> f32 {
-        (self.x * self.x + self.y * self.y).sqrt()
+    /// # `cast`
+    /// Converts the vector to another `Float` type, may round
+    pub fn cast<U: Float>(&self) -> Vector2<U> {
+        Vector2::new(U::from_f64(self.x.to_f64()), U::from_f64(self.y.to_f64()))
+    }
+
+    pub fn norm(&self) -> T {
+        self.dot(*self).sqrt()
+    }
+
+    /// # `dot`
+    /// Returns the dot product of the two vectors
+    pub fn dot(&self, other: Vector2<T>) -> T {
+        self.x * other.x + self.y * other.y
+    }
+
+    /// # `cross`
+    /// Returns the z component of the cross product of the two vectors (as if they were 3D with z = 0),
+    /// positive when `other` is counter clockwise from this vector
+    pub fn cross(&self, other: Vector2<T>) -> T {
+        self.x * other.y - self.y * other.x
+    }
+
+    /// # `normalize`
+    /// Returns the vector of length 1 in the same direction, the zero vector is returned unchanged
+    pub fn normalize(&self) -> Vector2<T> {
+        let norm = self.norm();
+        if norm == T::zero() {
+            return *self;
+        }
+        *self / norm
+    }
+
+    /// # `lerp`
+    /// Linearly interpolates from this vector (`t` = 0) to `other` (`t` = 1)
+    pub fn lerp(&self, other: Vector2<T>, t: T) -> Vector2<T> {
+        *self + (other - *self) * t
+    }
+
+    /// # `angle`
+    /// Returns the angle of the vector from the x axis in radians, in the range [-π, π]
+    pub fn angle(&self) -> T {
+        self.y.atan2(self.x)
+    }
+
+    /// # `rotate`
+    /// Returns the vector rotated around the origin by the given angle in radians
+    pub fn rotate(&self, radians: T) -> Vector2<T> {
+        Matrix2::rotation(radians) * *self
+    }
+
+    /// # `complex_mul`
+    /// Treats both vectors as complex numbers (x + yi) and returns their product
+    pub fn complex_mul(&self, other: Vector2<T>) -> Vector2<T> {
+        Vector2 {
+            x: self.x * other.x - self.y * other.y,
+            y: self.x * other.y + self.y * other.x
+        }
+    }
+
+    /// # `complex_div`
+    /// Treats both vectors as complex numbers (x + yi) and returns their quotient
+    pub fn complex_div(&self, other: Vector2<T>) -> Vector2<T> {
+        let denominator = other.x * other.x + other.y * other.y;
+        Vector2 {
+            x: (self.x * other.x + self.y * other.y) / denominator,
+            y: (self.y * other.x - self.x * other.y) / denominator
+        }
     }
 }
 
 /// Implement vector + vector
-impl ops::Add<Vector2> for Vector2 {
-    type Output = Vector2;
+impl<T: Float> ops::Add<Vector2<T>> for Vector2<T> {
+    type Output = Vector2<T>;
 
-    fn add(self, _rhs: Vector2) -> Vector2 {
+    fn add(self, _rhs: Vector2<T>) -> Vector2<T> {
         Vector2 {
             x: self.x + _rhs.x, 
             y: self.y + _rhs.y
@@ -43,10 +110,10 @@ impl ops::Add<Vector2> for Vector2 {
 }
 
 /// Implement vector - vector
-impl ops::Sub<Vector2> for Vector2 {
-    type Output = Vector2;
+impl<T: Float> ops::Sub<Vector2<T>> for Vector2<T> {
+    type Output = Vector2<T>;
 
-    fn sub(self, _rhs: Vector2) -> Vector2 {
+    fn sub(self, _rhs: Vector2<T>) -> Vector2<T> {
         Vector2 {
             x: self.x - _rhs.x, 
             y: self.y - _rhs.y
@@ -54,11 +121,11 @@ impl ops::Sub<Vector2> for Vector2 {
     }
 }
 
-/// Implement Vector * scalar multiplication
-impl ops::Mul<f32> for Vector2 {
-    type Output = Vector2;
+/// Implement Vector * scalar multiplication, scalar * Vector is implemented by `scalar_ops!`
+impl<T: Float> ops::Mul<T> for Vector2<T> {
+    type Output = Vector2<T>;
 
-    fn mul(self, _rhs: f32) -> Vector2 {
+    fn mul(self, _rhs: T) -> Vector2<T> {
         Vector2 {
             x: self.x * _rhs,
             y: self.y * _rhs 
@@ -66,19 +133,11 @@ impl ops::Mul<f32> for Vector2 {
     }
 }
 
-/// Implement scalar * vector multiplication
-impl ops::Mul<Vector2> for f32 {
-    type Output = Vector2;
-    fn mul (self, _rhs: Vector2) -> Vector2 {
-        _rhs * self
-    }
-}
-
 /// Implement Vector / Scalar division
-impl ops::Div<f32> for Vector2 {
-    type Output = Vector2;
+impl<T: Float> ops::Div<T> for Vector2<T> {
+    type Output = Vector2<T>;
 
-    fn div(self, _rhs: f32) -> Vector2 {
+    fn div(self, _rhs: T) -> Vector2<T> {
         Vector2 {
             x: self.x / _rhs ,
             y: self.y / _rhs 
@@ -87,10 +146,10 @@ impl ops::Div<f32> for Vector2 {
 }
 
 /// Implement Matrix * Vector multiplication
-impl ops::Mul<Vector2> for Matrix2 {
-    type Output = Vector2;
+impl<T: Float> ops::Mul<Vector2<T>> for Matrix2<T> {
+    type Output = Vector2<T>;
 
-    fn mul(self, _rhs: Vector2) -> Vector2 {
+    fn mul(self, _rhs: Vector2<T>) -> Vector2<T> {
         Vector2 {
             x: self.c11 * _rhs.x + self.c12 * _rhs.y,
             y: self.c21 * _rhs.x + self.c22 * _rhs.y
@@ -99,10 +158,10 @@ impl ops::Mul<Vector2> for Matrix2 {
 }
 
 /// Implement -vector
-impl ops::Neg<> for Vector2 {
-    type Output = Vector2;
+impl<T: Float> ops::Neg<> for Vector2<T> {
+    type Output = Vector2<T>;
 
-    fn neg(self) -> Vector2 {
+    fn neg(self) -> Vector2<T> {
         Vector2 {
             x: -self.x,
             y: -self.y
@@ -0,0 +1,103 @@
+use super::{
+    matrix::Matrix2,
+    vector::Vector2,
+    transform::Transform2,
+    float::Float
+};
+
+/// # `Viewport`
+/// Maps between pixels on the screen and points in the world (such as the complex plane).
+/// The view is centred on `centre`, `scale` world units wide and rotated by `rotation` radians around its centre.
+/// Of `f32` unless another `Float` type is given, the Mandelbrot set uses `f64` to zoom deeper
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct Viewport<T = f32> {
+    pub centre: Vector2<T>,
+    pub scale: T, // Width of the view in world units, the height follows from the aspect ratio of the screen
+    pub rotation: T,
+    pub screen: Vector2<T> // Size of the screen in pixels
+}
+
+impl<T: Float> Viewport<T> {
+    /// # `new`
+    /// Creates an unrotated viewport by taking its centre `Vector2`, its width in world units and the size of the screen in pixels `Vector2`
+    pub fn new(centre: Vector2<T>, scale: T, screen: Vector2<T>) -> Viewport<T> {
+        Viewport {
+            centre: centre,
+            scale: scale,
+            rotation: T::zero(),
+            screen: screen
+        }
+    }
+
+    /// # `pixel_size`
+    /// Returns the size of one pixel in world units
+    pub fn pixel_size(&self) -> T {
+        self.scale / self.screen.x
+    }
+
+    /// # `matrix`
+    /// Returns the matrix that takes a pixel offset from the centre of the screen to a world offset from the centre of the view
+    pub fn matrix(&self) -> Matrix2<T> {
+        Matrix2::rotation(self.rotation) * self.pixel_size()
+    }
+
+    /// # `transform`
+    /// Returns the transform that takes a pixel position on the screen to its point in the world
+    pub fn transform(&self) -> Transform2<T> {
+        Transform2::translation(self.centre) * Transform2::from_matrix(self.matrix()) * Transform2::translation(-self.half_screen())
+    }
+
+    /// # `half_screen`
+    /// Returns the pixel position of the centre of the screen
+    fn half_screen(&self) -> Vector2<T> {
+        self.screen / T::from_f64(2.0)
+    }
+
+    /// # `pixel_to_world`
+    /// Maps a pixel position on the screen to its point in the world. The offset from the centre is taken first so that
+    /// deep zooms do not lose the pixel offset against the centre, which `transform` would add in two steps
+    pub fn pixel_to_world(&self, pixel: Vector2<T>) -> Vector2<T> {
+        self.matrix() * (pixel - self.half_screen()) + self.centre
+    }
+
+    /// # `screen_map`
+    /// Returns the map from points in the world to pixels for the view as it is now, so that the matrix is inverted once
+    /// for all the points of a frame. `None` if the view has no size (a scale of 0), since then every point is on the same pixel
+    pub fn screen_map(&self) -> Option<ScreenMap<T>> {
+        Some(ScreenMap {
+            to_screen: Transform2::translation(self.half_screen()) * Transform2::from_matrix(self.matrix()).inverse()?,
+            centre: self.centre
+        })
+    }
+
+    /// # `pan`
+    /// Moves the view by the given amount of pixels `Vector2`, along the rotated axes of the view
+    pub fn pan(&mut self, pixels: Vector2<T>) {
+        self.centre = self.centre + self.transform().transform_direction(pixels);
+    }
+
+    /// # `zoom_at`
+    /// Multiplies the scale by `factor` while keeping the world point under the `pixel` in place
+    pub fn zoom_at(&mut self, pixel: Vector2<T>, factor: T) {
+        let anchor = self.pixel_to_world(pixel);
+        self.scale = self.scale * factor;
+        self.centre = self.centre + (anchor - self.pixel_to_world(pixel));
+    }
+}
+
+/// # `ScreenMap`
+/// Maps points in the world to pixels on the screen for a fixed view, made by `Viewport::screen_map`
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct ScreenMap<T = f32> {
+    to_screen: Transform2<T>, // Takes a world offset from the centre of the view to a pixel
+    centre: Vector2<T>
+}
+
+impl<T: Float> ScreenMap<T> {
+    /// # `world_to_pixel`
+    /// Maps a point in the world to its pixel position on the screen, the inverse of `Viewport::pixel_to_world`. The offset from the centre is taken first,
+    /// like in `Viewport::pixel_to_world`, so that deep zooms do not lose it against the centre
+    pub fn world_to_pixel(&self, world: Vector2<T>) -> Vector2<T> {
+        self.to_screen.transform_point(world - self.centre)
+    }
+}
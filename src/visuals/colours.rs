@@ -5,5 +5,49 @@ pub const GREEN : u32 = 0x00FF00;
 pub const BLUE : u32 = 0x0000FF;
 pub const CERISE : u32 = 0xE83D84;
 pub const RUST : u32 = 0xF74C00;
+pub const GOLD : u32 = 0xFFD700;
+pub const NAVY : u32 = 0x000080;
 
-pub const LIGHT_THEME : [u32; 6] = [0xACDDDE, 0xCAF1DE, 0xE1F8DC, 0xFEF8DD, 0xFFE7C7, 0xF7D8BA];
\ No newline at end of file
+pub const LIGHT_THEME : [u32; 6] = [0xACDDDE, 0xCAF1DE, 0xE1F8DC, 0xFEF8DD, 0xFFE7C7, 0xF7D8BA];
+
+/// # `gradient`
+/// Linearly blends the colour `from` into the colour `to` by taking `t` in the range [0, 1] (clamped) 
+/// and returns the blended colour as `u32`
+pub fn gradient(from: u32, to: u32, t: f32) -> u32 {
+    let t = t.clamp(0.0, 1.0);
+    let mut colour = 0;
+
+    // Blend each of the 8 bit channels separately (blue, green then red)
+    for shift in [0, 8, 16] {
+        let a = ((from >> shift) & 0xFF) as f32;
+        let b = ((to >> shift) & 0xFF) as f32;
+        colour |= ((a + (b - a) * t).round() as u32) << shift;
+    }
+
+    colour
+}
+
+/// # `average`
+/// Takes a slice of colours and returns their average colour, each channel is averaged separately
+pub fn average(colours: &[u32]) -> u32 {
+    if colours.is_empty() {
+        return BLACK;
+    }
+
+    let mut colour = 0;
+    for shift in [0, 8, 16] {
+        let sum : u32 = colours.iter().map(|c| (c >> shift) & 0xFF).sum();
+        colour |= ((sum as f32 / colours.len() as f32).round() as u32) << shift;
+    }
+
+    colour
+}
+
+/// # `difference`
+/// Returns the largest difference between the channels of two colours, in the range [0, 255]
+pub fn difference(a: u32, b: u32) -> u32 {
+    [0, 8, 16].iter()
+        .map(|shift| (((a >> shift) & 0xFF) as i32 - ((b >> shift) & 0xFF) as i32).unsigned_abs())
+        .max()
+        .unwrap()
+}
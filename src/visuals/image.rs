@@ -0,0 +1,98 @@
+use std::fs::File;
+use std::io::{Error, ErrorKind, Read, Write};
+
+/// # `Image`
+/// A simple RGB image stored the same way as the window buffer (one `u32` per pixel, 0xRRGGBB)
+pub struct Image {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<u32>
+}
+
+impl Image {
+    /// # `load_ppm`
+    /// Loads a PPM image (binary P6 or plain text P3) from the given path.
+    /// Returns an error if the file can not be read or is not a valid PPM image
+    pub fn load_ppm(path: &str) -> std::io::Result<Image> {
+        let mut bytes = Vec::new();
+        File::open(path)?.read_to_end(&mut bytes)?;
+
+        // The header is 4 whitespace separated tokens (magic, width, height, max value), '#' starts a comment
+        let mut tokens : Vec<String> = Vec::new();
+        let mut idx = 0;
+        while tokens.len() < 4 && idx < bytes.len() {
+            match bytes[idx] {
+                b'#' => while idx < bytes.len() && bytes[idx] != b'\n' { idx += 1; },
+                b if b.is_ascii_whitespace() => idx += 1,
+                _ => {
+                    let start = idx;
+                    while idx < bytes.len() && !bytes[idx].is_ascii_whitespace() { idx += 1; }
+                    tokens.push(String::from_utf8_lossy(&bytes[start..idx]).to_string());
+                }
+            }
+        }
+
+        let invalid = |message: &str| Error::new(ErrorKind::InvalidData, format!("\"{}\": {}", path, message));
+        if tokens.len() < 4 {
+            return Err(invalid("incomplete PPM header"));
+        }
+
+        let number = |token: &String| token.parse::<usize>().map_err(|_| invalid("invalid number in PPM header"));
+        let width = number(&tokens[1])?;
+        let height = number(&tokens[2])?;
+        let max_value = number(&tokens[3])?;
+        if width == 0 || height == 0 {
+            return Err(invalid("the image has no pixels"));
+        }
+        if max_value == 0 || max_value > 255 {
+            return Err(invalid("only 8 bit PPM images are supported"));
+        }
+
+        // Channel values, scaled to the range [0, 255]
+        let values : Vec<u32> = match tokens[0].as_str() {
+            "P6" => bytes.get(idx + 1..).unwrap_or(&[]).iter().map(|v| *v as u32).collect(),
+            "P3" => String::from_utf8_lossy(&bytes[idx..])
+                .split_ascii_whitespace()
+                .map(|v| v.parse::<u32>().map_err(|_| invalid("invalid pixel value")))
+                .collect::<std::io::Result<Vec<u32>>>()?,
+            _ => return Err(invalid("not a P3 or P6 PPM image"))
+        };
+
+        if values.len() < width * height * 3 {
+            return Err(invalid("not enough pixel data"));
+        }
+
+        let pixels = values.chunks_exact(3)
+            .take(width * height)
+            .map(|rgb| {
+                let scale = |v: u32| v.min(max_value as u32) * 255 / max_value as u32;
+                (scale(rgb[0]) << 16) | (scale(rgb[1]) << 8) | scale(rgb[2])
+            })
+            .collect();
+
+        Ok(Image {
+            width: width,
+            height: height,
+            pixels: pixels
+        })
+    }
+
+    /// # `save_ppm`
+    /// Saves the image as a binary (P6) PPM image at the given path
+    pub fn save_ppm(&self, path: &str) -> std::io::Result<()> {
+        let mut bytes = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        for pixel in self.pixels.iter() {
+            bytes.extend_from_slice(&[(pixel >> 16) as u8, (pixel >> 8) as u8, *pixel as u8]);
+        }
+
+        File::create(path)?.write_all(&bytes)
+    }
+
+    /// # `sample`
+    /// Gets the colour of the pixel at the texture coordinates `u` and `v`, both in the range [0, 1]
+    pub fn sample(&self, u: f32, v: f32) -> u32 {
+        let x = ((u * self.width as f32) as usize).min(self.width - 1);
+        let y = ((v * self.height as f32) as usize).min(self.height - 1);
+        self.pixels[x + y * self.width]
+    }
+}
@@ -1,2 +1,3 @@
 pub mod visualizer;
-pub mod colours;
\ No newline at end of file
+pub mod colours;
+pub mod image;
\ No newline at end of file
@@ -2,6 +2,7 @@ extern crate minifb;
 use minifb::{Window, WindowOptions, Key, MouseButton, MouseMode};
 
 use crate::math::vector::*;
+use crate::math::transform::Transform2;
 
 // Screen size
 pub const WIDTH : f32 = 800.0;
@@ -12,10 +13,14 @@ pub const U_WIDTH : usize = WIDTH as usize;
 pub const U_HEIGHT : usize = HEIGHT as usize;
 
 /// # `Visualizer`
-/// Struct used to manipulate the pixel buffer of the screen
+/// Struct used to manipulate the pixel buffer of the screen.
+/// Lines and circles are drawn in local coordinates which are mapped to the screen through the current transform,
+/// the transform can be saved with `push` and restored with `pop`. Pixels are always set in screen coordinates
 pub struct Visualizer {
     pub buffer: Vec<u32>,
-    pub window: Window
+    pub window: Window,
+    transform: Transform2, // Maps local coordinates to the screen
+    transforms: Vec<Transform2> // Transforms saved by `push`
 }
 
 impl Visualizer {
@@ -29,7 +34,9 @@ impl Visualizer {
             window: Window::new("Fractal Visualizer", 
                                 WIDTH as usize, 
                                 HEIGHT as usize, 
-                                WindowOptions::default()).unwrap()
+                                WindowOptions::default()).unwrap(),
+            transform: Transform2::identity(),
+            transforms: Vec::new()
         };
 
         vis.window.limit_update_rate(
@@ -100,9 +107,12 @@ impl Visualizer {
 
 
     /// # `draw_line`
-    /// Takes a starting position `Vector2` and an terminal position `Vector2` and a colour `(u32)` and thickness `usize`
+    /// Takes a starting position `Vector2` and an terminal position `Vector2` (both in local coordinates) and a colour `(u32)` and thickness `usize`
     /// then draws a line from a starting point to an terminal point using Bersenham Line Algorithm
     pub fn draw_line(&mut self, start: Vector2, end: Vector2, colour: u32, thickness: usize) -> Result<(), &'static str> {
+        let start = self.transform.transform_point(start);
+        let end = self.transform.transform_point(end);
+
         for thick in 0..thickness {
             let thick_coord = Vector2::new(thick as f32, thick as f32);
             self.draw_line_at(
@@ -133,9 +143,12 @@ impl Visualizer {
     }
 
     /// # `draw_circle`
-    /// Takes a center `Vector2` and a radius `Vector2` and a colour `(u32)` 
-    /// and draws a circle using the Bersenham Circle Algorithm
+    /// Takes a center `Vector2` and a radius `Vector2` (both in local coordinates) and a colour `(u32)` 
+    /// and draws a circle using the Bersenham Circle Algorithm. The radius is scaled by the average scale of the transform
     pub fn draw_circle(&mut self, center: Vector2, radius: f32, colour: u32) {
+        let center = self.transform.transform_point(center);
+        let radius = radius * self.transform.matrix.determinant().abs().sqrt();
+
         let mut x = 0.0;
         let mut y = radius;
         let mut d = 3.0 - 2.0 * radius;
@@ -155,6 +168,51 @@ impl Visualizer {
         }
     }
 
+    // === TRANSFORM STACK ===
+
+    /// # `push`
+    /// Saves the current transform, it is restored by the matching `pop`
+    pub fn push(&mut self) {
+        self.transforms.push(self.transform);
+    }
+
+    /// # `pop`
+    /// Restores the transform saved by the last `push`, or the identity if there is none
+    pub fn pop(&mut self) {
+        self.transform = self.transforms.pop().unwrap_or_else(Transform2::identity);
+    }
+
+    /// # `reset_transform`
+    /// Goes back to drawing in screen coordinates and forgets every saved transform
+    pub fn reset_transform(&mut self) {
+        self.transform = Transform2::identity();
+        self.transforms.clear();
+    }
+
+    /// # `apply_transform`
+    /// Applies the given transform in the current local coordinates, everything drawn afterwards is first mapped through it
+    pub fn apply_transform(&mut self, transform: Transform2) {
+        self.transform = self.transform * transform;
+    }
+
+    /// # `translate`
+    /// Moves the local origin by the given offset `Vector2` in local coordinates
+    pub fn translate(&mut self, offset: Vector2) {
+        self.apply_transform(Transform2::translation(offset));
+    }
+
+    /// # `rotate`
+    /// Rotates the local coordinates around the local origin by the given angle in radians
+    pub fn rotate(&mut self, radians: f32) {
+        self.apply_transform(Transform2::rotation(radians));
+    }
+
+    /// # `scale`
+    /// Scales the local coordinates from the local origin by the given factor
+    pub fn scale(&mut self, factor: f32) {
+        self.apply_transform(Transform2::scale(factor, factor));
+    }
+
     /// # `apply_buffer`
     /// Draws the content of the buffer unto the window
     pub fn apply_buffer(&mut self) {